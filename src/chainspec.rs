@@ -0,0 +1,355 @@
+//! Limits of the target network, read from its chainspec.toml.
+//!
+//! Only the fields the generator needs are read, so chainspecs of any network
+//! (mainnet, testnet or a custom one) can be used as they are.
+
+use std::{convert::TryFrom, fmt::Display, fs};
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_node::types::{Deploy, DeployConfig};
+use casper_types::{bytesrepr::ToBytes, system::mint::ARG_AMOUNT, CLValue, TimeDiff, U512};
+use serde::Deserialize;
+
+/// Environment variable with the path to the chainspec.toml of the target network.
+pub(crate) const CHAINSPEC_PATH_ENV: &str = "CL_CHAINSPEC";
+
+/// Chainspec used when `CL_CHAINSPEC` is not set.
+const DEFAULT_CHAINSPEC: &str = include_str!("../resources/chainspec.toml");
+
+#[derive(Deserialize)]
+struct RawChainspec {
+    network: RawNetwork,
+    core: RawCore,
+    // Read twice - into casper-node's `DeployConfig` for the validity oracle
+    // and into `RawDeploys` for the limits the samples are generated with.
+    deploys: toml::Value,
+}
+
+#[derive(Deserialize)]
+struct RawNetwork {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct RawCore {
+    max_associated_keys: u32,
+}
+
+#[derive(Deserialize)]
+struct RawDeploys {
+    max_ttl: TimeDiff,
+    max_dependencies: u8,
+    max_deploy_size: u32,
+    payment_args_max_length: u32,
+    session_args_max_length: u32,
+    native_transfer_minimum_motes: u64,
+    block_gas_limit: u64,
+}
+
+/// Chainspec limits that the generated deploys are subject to.
+#[derive(Clone)]
+pub(crate) struct ChainspecLimits {
+    chain_name: String,
+    max_associated_keys: u32,
+    max_ttl: TimeDiff,
+    max_dependencies: u8,
+    max_deploy_size: u32,
+    payment_args_max_length: u32,
+    session_args_max_length: u32,
+    native_transfer_minimum_motes: u64,
+    block_gas_limit: u64,
+    deploy_config: DeployConfig,
+}
+
+/// Chainspec limit a deploy exceeds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum LimitError {
+    DeploySize {
+        max: u32,
+        found: usize,
+    },
+    Ttl {
+        max: TimeDiff,
+        found: TimeDiff,
+    },
+    Dependencies {
+        max: u8,
+        found: usize,
+    },
+    Approvals {
+        max: u32,
+        found: usize,
+    },
+    PaymentArgsLength {
+        max: u32,
+        found: usize,
+    },
+    SessionArgsLength {
+        max: u32,
+        found: usize,
+    },
+    /// Native transfer of less than the minimum amount.
+    TransferAmount {
+        min: U512,
+        found: U512,
+    },
+    /// Payment buys more gas than fits into a block.
+    Gas {
+        max: u64,
+        found: U512,
+    },
+}
+
+impl Display for LimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitError::DeploySize { max, found } => {
+                write!(f, "deploy size {} above the maximum {}", found, max)
+            }
+            LimitError::Ttl { max, found } => write!(f, "TTL {} above the maximum {}", found, max),
+            LimitError::Dependencies { max, found } => {
+                write!(f, "{} dependencies, at most {} allowed", found, max)
+            }
+            LimitError::Approvals { max, found } => {
+                write!(f, "{} approvals, at most {} allowed", found, max)
+            }
+            LimitError::PaymentArgsLength { max, found } => {
+                write!(f, "payment args length {} above the maximum {}", found, max)
+            }
+            LimitError::SessionArgsLength { max, found } => {
+                write!(f, "session args length {} above the maximum {}", found, max)
+            }
+            LimitError::TransferAmount { min, found } => {
+                write!(f, "transfer amount {} below the minimum {}", found, min)
+            }
+            LimitError::Gas { max, found } => {
+                write!(f, "gas {} above the block gas limit {}", found, max)
+            }
+        }
+    }
+}
+
+impl ChainspecLimits {
+    /// Loads the chainspec pointed to by `CL_CHAINSPEC`, or the default one when it's not set.
+    pub(crate) fn load() -> Self {
+        match std::env::var(CHAINSPEC_PATH_ENV) {
+            Ok(path) => {
+                let chainspec = fs::read_to_string(&path)
+                    .unwrap_or_else(|err| panic!("failed to read chainspec {}: {}", path, err));
+                Self::from_toml(&chainspec)
+            }
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub(crate) fn from_toml(chainspec: &str) -> Self {
+        let raw: RawChainspec = toml::from_str(chainspec).expect("chainspec should be valid TOML");
+        let deploys: RawDeploys = raw
+            .deploys
+            .clone()
+            .try_into()
+            .expect("chainspec should contain deploy limits");
+        let deploy_config: DeployConfig = raw
+            .deploys
+            .try_into()
+            .expect("chainspec should contain valid deploy config");
+        ChainspecLimits {
+            chain_name: raw.network.name,
+            max_associated_keys: raw.core.max_associated_keys,
+            max_ttl: deploys.max_ttl,
+            max_dependencies: deploys.max_dependencies,
+            max_deploy_size: deploys.max_deploy_size,
+            payment_args_max_length: deploys.payment_args_max_length,
+            session_args_max_length: deploys.session_args_max_length,
+            native_transfer_minimum_motes: deploys.native_transfer_minimum_motes,
+            block_gas_limit: deploys.block_gas_limit,
+            deploy_config,
+        }
+    }
+
+    pub(crate) fn chain_name(&self) -> &str {
+        &self.chain_name
+    }
+
+    pub(crate) fn max_associated_keys(&self) -> u32 {
+        self.max_associated_keys
+    }
+
+    /// Maximum number of approvals of a single deploy - capped at `u8::MAX` keys the generator can create.
+    pub(crate) fn max_approvals(&self) -> u8 {
+        u8::try_from(self.max_associated_keys).unwrap_or(u8::MAX)
+    }
+
+    pub(crate) fn max_ttl(&self) -> TimeDiff {
+        self.max_ttl
+    }
+
+    pub(crate) fn max_dependencies(&self) -> u8 {
+        self.max_dependencies
+    }
+
+    /// Maximum size of a serialized deploy, in bytes.
+    pub(crate) fn max_deploy_size(&self) -> u32 {
+        self.max_deploy_size
+    }
+
+    /// Maximum size of the serialized payment args, in bytes.
+    pub(crate) fn payment_args_max_length(&self) -> u32 {
+        self.payment_args_max_length
+    }
+
+    /// Maximum size of the serialized session args, in bytes.
+    pub(crate) fn session_args_max_length(&self) -> u32 {
+        self.session_args_max_length
+    }
+
+    /// Minimum amount of a native transfer, in motes.
+    pub(crate) fn native_transfer_minimum_motes(&self) -> u64 {
+        self.native_transfer_minimum_motes
+    }
+
+    pub(crate) fn deploy_config(&self) -> &DeployConfig {
+        &self.deploy_config
+    }
+
+    /// Checks the deploy against the limits - the same ones casper-node checks it against,
+    /// except for the chain name.
+    ///
+    /// Well-known arguments that are missing or mistyped are left to `parser::validation`.
+    pub(crate) fn check(&self, deploy: &Deploy) -> Result<(), LimitError> {
+        let header = deploy.header();
+        let size = deploy.serialized_length();
+        if size > self.max_deploy_size as usize {
+            return Err(LimitError::DeploySize {
+                max: self.max_deploy_size,
+                found: size,
+            });
+        }
+        if header.ttl() > self.max_ttl {
+            return Err(LimitError::Ttl {
+                max: self.max_ttl,
+                found: header.ttl(),
+            });
+        }
+        if header.dependencies().len() > self.max_dependencies as usize {
+            return Err(LimitError::Dependencies {
+                max: self.max_dependencies,
+                found: header.dependencies().len(),
+            });
+        }
+        if deploy.approvals().len() > self.max_associated_keys as usize {
+            return Err(LimitError::Approvals {
+                max: self.max_associated_keys,
+                found: deploy.approvals().len(),
+            });
+        }
+        let payment_args_length = deploy.payment().args().serialized_length();
+        if payment_args_length > self.payment_args_max_length as usize {
+            return Err(LimitError::PaymentArgsLength {
+                max: self.payment_args_max_length,
+                found: payment_args_length,
+            });
+        }
+        let session_args_length = deploy.session().args().serialized_length();
+        if session_args_length > self.session_args_max_length as usize {
+            return Err(LimitError::SessionArgsLength {
+                max: self.session_args_max_length,
+                found: session_args_length,
+            });
+        }
+        match deploy.session() {
+            ExecutableDeployItem::Transfer { args } => {
+                let min = U512::from(self.native_transfer_minimum_motes);
+                match amount(args.get(ARG_AMOUNT)) {
+                    Some(found) if found < min => Err(LimitError::TransferAmount { min, found }),
+                    _ => Ok(()),
+                }
+            }
+            // Gas price of zero doesn't buy any gas.
+            _ if header.gas_price() == 0 => Ok(()),
+            _ => {
+                let gas = amount(deploy.payment().args().get(ARG_AMOUNT))
+                    .map(|payment| payment / U512::from(header.gas_price()));
+                match gas {
+                    Some(found) if found > U512::from(self.block_gas_limit) => {
+                        Err(LimitError::Gas {
+                            max: self.block_gas_limit,
+                            found,
+                        })
+                    }
+                    _ => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// The `U512` amount, if it's there and of the right type.
+fn amount(cl_value: Option<&CLValue>) -> Option<U512> {
+    cl_value.and_then(|cl_value| cl_value.clone().into_t().ok())
+}
+
+impl Default for ChainspecLimits {
+    /// Limits of the bundled chainspec, regardless of `CL_CHAINSPEC`.
+    fn default() -> Self {
+        Self::from_toml(DEFAULT_CHAINSPEC)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+    use casper_node::types::Deploy;
+    use casper_types::{
+        bytesrepr::Bytes, runtime_args, RuntimeArgs, SecretKey, TimeDiff, Timestamp, U512,
+    };
+
+    use super::{ChainspecLimits, LimitError};
+
+    #[test]
+    fn default_chainspec_limits() {
+        let limits = ChainspecLimits::default();
+        assert_eq!(limits.chain_name(), "mainnet");
+        assert_eq!(limits.max_ttl(), TimeDiff::from_seconds(60 * 60 * 24));
+        assert_eq!(limits.max_dependencies(), 10);
+        assert_eq!(limits.max_approvals(), 100);
+        assert_eq!(limits.max_deploy_size(), 1_048_576);
+        assert_eq!(limits.payment_args_max_length(), 1024);
+        assert_eq!(limits.session_args_max_length(), 1024);
+        assert_eq!(limits.native_transfer_minimum_motes(), 2_500_000_000);
+    }
+    #[test]
+    fn transfer_below_minimum_exceeds_limits() {
+        let limits = ChainspecLimits::default();
+        let transfer = |amount: u64| {
+            Deploy::new(
+                Timestamp::now(),
+                TimeDiff::from_seconds(60),
+                1,
+                vec![],
+                limits.chain_name().to_string(),
+                ExecutableDeployItem::ModuleBytes {
+                    module_bytes: Bytes::new(),
+                    args: runtime_args! { "amount" => U512::from(1_000_000_000u64) },
+                },
+                ExecutableDeployItem::Transfer {
+                    args: runtime_args! {
+                        "amount" => U512::from(amount),
+                        "id" => Some(1u64),
+                        "target" => [1u8; 32]
+                    },
+                },
+                &SecretKey::ed25519_from_bytes([1u8; 32]).unwrap(),
+                None,
+            )
+        };
+        assert_eq!(
+            limits.check(&transfer(2_499_999_999)),
+            Err(LimitError::TransferAmount {
+                min: U512::from(2_500_000_000u64),
+                found: U512::from(2_499_999_999u64),
+            })
+        );
+        assert_eq!(limits.check(&transfer(2_500_000_000)), Ok(()));
+    }
+}
@@ -0,0 +1,302 @@
+//! Coverage matrix of the test vectors - which combinations of executable item variant, phase,
+//! recognized deploy type, validity and key algorithm are covered by at least one sample,
+//! and which are missing.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_node::types::Deploy;
+use casper_types::{system::mint::ARG_TARGET, CLType, CLValue, PublicKey};
+use itertools::Itertools;
+
+use crate::{utils::variant_name, vectors::TestVector};
+
+const VARIANTS: [&str; 6] = [
+    "ModuleBytes",
+    "StoredContractByHash",
+    "StoredContractByName",
+    "StoredVersionedContractByHash",
+    "StoredVersionedContractByName",
+    "Transfer",
+];
+const PHASES: [&str; 2] = ["Payment", "Session"];
+const VALIDITY: [&str; 2] = ["valid", "invalid"];
+const ALGORITHMS: [&str; 2] = ["ed25519", "secp256k1"];
+/// Item cells no sample can land in - `validate_placement` rejects every transfer used as payment.
+const ITEMS_NOT_APPLICABLE: [[&str; 3]; 1] = [["Payment", "Transfer", "valid"]];
+
+/// Recognized deploy types (the `Type` element), with the roles of public keys they're expected to carry.
+const TYPE_KEY_ROLES: [(&str, &[&str]); 5] = [
+    ("Delegate", &["account", "signer", "delegator", "validator"]),
+    (
+        "Undelegate",
+        &["account", "signer", "delegator", "validator"],
+    ),
+    (
+        "Redelegate",
+        &[
+            "account",
+            "signer",
+            "delegator",
+            "validator",
+            "new_validator",
+        ],
+    ),
+    ("Token transfer", &["account", "signer", ARG_TARGET]),
+    ("Contract execution", &["account", "signer"]),
+];
+
+/// Number of samples in every cell of the cartesian product of expected dimension values.
+struct Matrix {
+    title: &'static str,
+    dimensions: Vec<&'static str>,
+    counts: BTreeMap<Vec<String>, usize>,
+    not_applicable: BTreeSet<Vec<String>>,
+}
+
+impl Matrix {
+    /// Creates the matrix with all the `cells` expected to be covered.
+    fn new(title: &'static str, dimensions: &[&'static str], cells: Vec<Vec<String>>) -> Self {
+        Matrix {
+            title,
+            dimensions: dimensions.to_vec(),
+            counts: cells.into_iter().map(|cell| (cell, 0)).collect(),
+            not_applicable: BTreeSet::new(),
+        }
+    }
+
+    /// Marks the `cells` as structurally impossible, so they're displayed as `n/a` instead of holes.
+    fn not_applicable(mut self, cells: impl IntoIterator<Item = Vec<String>>) -> Self {
+        for cell in cells {
+            self.counts.remove(&cell);
+            self.not_applicable.insert(cell);
+        }
+        self
+    }
+
+    /// Creates the matrix expecting every combination of the `values` of the dimensions.
+    fn product(title: &'static str, dimensions: &[(&'static str, &[&str])]) -> Self {
+        let names: Vec<&'static str> = dimensions.iter().map(|(name, _)| *name).collect();
+        let cells = dimensions
+            .iter()
+            .map(|(_, values)| values.iter().map(|value| value.to_string()))
+            .multi_cartesian_product()
+            .collect();
+        Matrix::new(title, &names, cells)
+    }
+
+    /// Counts a sample in the `cell`. Cells that weren't expected are added as well.
+    fn record(&mut self, cell: Vec<String>) {
+        *self.counts.entry(cell).or_default() += 1;
+    }
+
+    fn holes(&self) -> impl Iterator<Item = &Vec<String>> {
+        self.counts
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(cell, _)| cell)
+    }
+}
+
+impl Display for Matrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "=== {} ({} of {} cells covered, {} n/a)",
+            self.title,
+            self.counts.len() - self.holes().count(),
+            self.counts.len(),
+            self.not_applicable.len()
+        )?;
+        writeln!(f, "{}", self.dimensions.join(" | "))?;
+        let mut rows: BTreeMap<&Vec<String>, String> = self
+            .counts
+            .iter()
+            .map(|(cell, count)| match count {
+                0 => (cell, "MISSING".to_string()),
+                count => (cell, count.to_string()),
+            })
+            .collect();
+        for cell in &self.not_applicable {
+            rows.entry(cell).or_insert_with(|| "n/a".to_string());
+        }
+        for (cell, count) in rows {
+            writeln!(f, "{} | {}", cell.join(" | "), count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Coverage of the deploy vectors.
+pub(crate) struct CoverageMatrix {
+    items: Matrix,
+    types: Matrix,
+    keys: Matrix,
+}
+
+impl CoverageMatrix {
+    /// Walks the deploy vectors and their parsed elements. Message vectors are skipped.
+    pub(crate) fn new(vectors: &[TestVector]) -> Self {
+        let type_names: Vec<&str> = TYPE_KEY_ROLES.iter().map(|(name, _)| *name).collect();
+        let mut matrix = CoverageMatrix {
+            items: Matrix::product(
+                "Executable items",
+                &[
+                    ("phase", &PHASES[..]),
+                    ("variant", &VARIANTS[..]),
+                    ("validity", &VALIDITY[..]),
+                ],
+            )
+            .not_applicable(ITEMS_NOT_APPLICABLE.iter().map(|values| cell(values))),
+            types: Matrix::product(
+                "Recognized types",
+                &[("type", &type_names[..]), ("validity", &VALIDITY[..])],
+            ),
+            keys: Matrix::new(
+                "Public keys",
+                &["type", "role", "algorithm"],
+                TYPE_KEY_ROLES
+                    .iter()
+                    .flat_map(|(name, roles)| {
+                        roles.iter().flat_map(move |role| {
+                            ALGORITHMS
+                                .iter()
+                                .map(move |algorithm| cell(&[*name, *role, *algorithm]))
+                        })
+                    })
+                    .collect(),
+            ),
+        };
+        for vector in vectors {
+            if let Some(sample) = vector.deploy() {
+                let deploy_type = vector
+                    .elements()
+                    .into_iter()
+                    .find(|element| element.name() == "Type")
+                    .map(|element| element.value().to_string())
+                    .expect("every deploy has a type");
+                matrix.record(sample.sample(), &deploy_type, sample.valid());
+            }
+        }
+        matrix
+    }
+
+    fn record(&mut self, deploy: &Deploy, deploy_type: &str, valid: bool) {
+        let validity = VALIDITY[if valid { 0 } else { 1 }];
+        for (phase, item) in PHASES.iter().zip([deploy.payment(), deploy.session()]) {
+            self.items
+                .record(cell(&[*phase, variant_name(item), validity]));
+        }
+        self.types.record(cell(&[deploy_type, validity]));
+        for (role, key) in public_keys(deploy) {
+            self.keys
+                .record(cell(&[deploy_type, role.as_str(), key_algorithm(&key)]));
+        }
+    }
+
+    /// Cells not covered by any sample, e.g. `no Payment-phase StoredVersionedContractByName invalid sample`.
+    pub(crate) fn holes(&self) -> Vec<String> {
+        let items = self
+            .items
+            .holes()
+            .map(|cell| format!("no {}-phase {} {} sample", cell[0], cell[1], cell[2]));
+        let types = self
+            .types
+            .holes()
+            .map(|cell| format!("no {} {} sample", cell[1], cell[0]));
+        let keys = self
+            .keys
+            .holes()
+            .map(|cell| format!("no {} {} in {}", cell[2], cell[1], cell[0]));
+        items.chain(types).chain(keys).collect()
+    }
+}
+
+impl Display for CoverageMatrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.items)?;
+        writeln!(f, "{}", self.types)?;
+        writeln!(f, "{}", self.keys)?;
+        let holes = self.holes();
+        writeln!(f, "=== Holes ({})", holes.len())?;
+        for hole in holes {
+            writeln!(f, "{}", hole)?;
+        }
+        Ok(())
+    }
+}
+
+fn cell(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+/// Public keys of the deploy with their roles: the account, the signers
+/// and the session arguments of `PublicKey` type, named after the argument.
+fn public_keys(deploy: &Deploy) -> Vec<(String, PublicKey)> {
+    let account = ("account".to_string(), deploy.header().account().clone());
+    let signers = deploy
+        .approvals()
+        .iter()
+        .map(|approval| ("signer".to_string(), approval.signer().clone()));
+    let args = session_args(deploy.session())
+        .filter(|(_, value)| value.cl_type() == &CLType::PublicKey)
+        .filter_map(|(name, value)| {
+            let key = value.clone().into_t::<PublicKey>().ok()?;
+            Some((name.to_string(), key))
+        });
+    std::iter::once(account)
+        .chain(signers)
+        .chain(args)
+        .collect()
+}
+
+fn session_args(item: &ExecutableDeployItem) -> impl Iterator<Item = (&str, &CLValue)> {
+    item.args()
+        .named_args()
+        .map(|arg| (arg.name(), arg.cl_value()))
+}
+
+fn key_algorithm(key: &PublicKey) -> &'static str {
+    match key {
+        PublicKey::Ed25519(_) => "ed25519",
+        PublicKey::Secp256k1(_) => "secp256k1",
+        _ => "system",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cell, Matrix};
+
+    #[test]
+    fn uncovered_cells_are_holes() {
+        let mut matrix = Matrix::product(
+            "test",
+            &[("phase", &["Payment", "Session"]), ("validity", &["valid"])],
+        );
+        matrix.record(cell(&["Session", "valid"]));
+        matrix.record(cell(&["Session", "valid"]));
+        // Unexpected cells are counted, but never reported as holes.
+        matrix.record(cell(&["Session", "invalid"]));
+
+        let holes: Vec<_> = matrix.holes().collect();
+        assert_eq!(holes, vec![&cell(&["Payment", "valid"])]);
+        assert_eq!(matrix.counts[&cell(&["Session", "valid"])], 2);
+    }
+
+    #[test]
+    fn impossible_cells_are_not_holes() {
+        let matrix = Matrix::product(
+            "test",
+            &[("phase", &["Payment", "Session"]), ("validity", &["valid"])],
+        )
+        .not_applicable(vec![cell(&["Payment", "valid"])]);
+
+        let holes: Vec<_> = matrix.holes().collect();
+        assert_eq!(holes, vec![&cell(&["Session", "valid"])]);
+        assert!(matrix.to_string().contains("Payment | valid | n/a"));
+    }
+}
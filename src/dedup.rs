@@ -0,0 +1,294 @@
+//! Deduplication of test vectors that look the same on the device.
+//!
+//! Random TTLs, key counts or amounts often don't change what the vector tests, so many vectors
+//! test exactly the same thing. Vectors are grouped by their validity, the pages of both modes
+//! that show what the deploy does and the structural fingerprint of their blob - only one
+//! representative of each group is kept.
+
+use std::collections::BTreeMap;
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_node::types::Deploy;
+use casper_types::bytesrepr::FromBytes;
+use itertools::Itertools;
+
+use crate::{ledger::ZondaxRepr, utils::variant_name};
+
+/// Environment variable selecting the deduplication policy:
+/// `off` (default), `first`, `smallest` or `largest`.
+pub(crate) const DEDUP_ENV: &str = "CL_DEDUP";
+
+/// Which vector of a group of duplicates is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DedupPolicy {
+    /// All the vectors are kept.
+    Off,
+    /// The first generated one.
+    KeepFirst,
+    /// The one with the smallest blob - the quickest to test on the device.
+    KeepSmallest,
+    /// The one with the largest blob - the most demanding for the device.
+    KeepLargest,
+}
+
+impl DedupPolicy {
+    /// Reads the policy from `CL_DEDUP`, defaults to `Off`.
+    pub(crate) fn from_env() -> Self {
+        match std::env::var(DEDUP_ENV).as_deref() {
+            Err(_) | Ok("off") => DedupPolicy::Off,
+            Ok("first") => DedupPolicy::KeepFirst,
+            Ok("smallest") => DedupPolicy::KeepSmallest,
+            Ok("largest") => DedupPolicy::KeepLargest,
+            Ok(other) => panic!("invalid {}: {}", DEDUP_ENV, other),
+        }
+    }
+}
+
+/// Vectors dropped in favor of the kept one.
+pub(crate) struct DuplicateGroup {
+    kept: String,
+    dropped: Vec<String>,
+}
+
+/// Pages that are unique to every vector (`Txn hash`) or show the random header fields and approvals
+/// of the samples, rather than what the deploy does.
+fn is_random_page(name: &str) -> bool {
+    matches!(
+        name,
+        "Txn hash"
+            | "Chain ID"
+            | "Account"
+            | "Timestamp"
+            | "Ttl"
+            | "Gas price"
+            | "Deps #"
+            | "Deps more"
+            | "Approvals #"
+    ) || name.starts_with("Dep ")
+        || name.starts_with("Signer ")
+}
+
+/// Returns the pages of the vector that show what it tests, without their indices
+/// (which shift along with the pages left out), e.g. `Amount : 100 motes`.
+///
+/// Valid vectors leave out the random pages. Header of an invalid vector may be what makes it
+/// invalid, so only its `Txn hash` page is left out.
+fn semantic_pages(pages: &[String], valid: bool) -> Vec<String> {
+    pages
+        .iter()
+        .map(|page| {
+            page.split_once(" | ")
+                .map_or(page.as_str(), |(_, page)| page)
+        })
+        .filter(|page| {
+            let (name, _) = page.split_once(" : ").unwrap_or((*page, ""));
+            // Drop the `[n/m]` suffix of the pages of long values.
+            let name = name.split(" [").next().unwrap_or(name);
+            if valid {
+                !is_random_page(name)
+            } else {
+                name != "Txn hash"
+            }
+        })
+        .map(|page| page.to_string())
+        .collect()
+}
+
+/// Returns the structural fingerprint of the vector's blob: variants and argument types of
+/// the payment and session. Blobs that are not deploys (messages) are fingerprinted by their length.
+fn blob_fingerprint(vector: &ZondaxRepr) -> String {
+    let bytes = hex::decode(vector.blob()).expect("blob should be hex-encoded");
+    match Deploy::from_bytes(&bytes) {
+        Ok((deploy, remainder)) if remainder.is_empty() => format!(
+            "payment {} | session {}",
+            item_fingerprint(deploy.payment()),
+            item_fingerprint(deploy.session()),
+        ),
+        _ => format!("{} bytes", bytes.len()),
+    }
+}
+
+fn item_fingerprint(item: &ExecutableDeployItem) -> String {
+    format!(
+        "{}({})",
+        variant_name(item),
+        item.args()
+            .named_args()
+            .map(|arg| format!("{}: {:?}", arg.name(), arg.cl_value().cl_type()))
+            .join(", ")
+    )
+}
+
+/// Keeps one vector of every group of duplicates, according to `policy`.
+///
+/// Kept vectors stay in their original order and keep their indices, so they can still be
+/// explained by index. Returns the kept vectors and the groups with dropped duplicates.
+pub(crate) fn deduplicate(
+    vectors: Vec<ZondaxRepr>,
+    policy: DedupPolicy,
+) -> (Vec<ZondaxRepr>, Vec<DuplicateGroup>) {
+    if policy == DedupPolicy::Off {
+        return (vectors, vec![]);
+    }
+
+    // Positions of the vectors in each group, in the order of generation.
+    let mut groups: BTreeMap<_, Vec<usize>> = BTreeMap::new();
+    for (position, vector) in vectors.iter().enumerate() {
+        let (output, output_expert) = vector.outputs();
+        let (valid, _) = vector.validity();
+        let key = (
+            vector.validity(),
+            semantic_pages(output, valid),
+            semantic_pages(output_expert, valid),
+            blob_fingerprint(vector),
+        );
+        groups.entry(key).or_default().push(position);
+    }
+
+    let blob_len = |position: &usize| vectors[*position].blob().len();
+    let mut kept = vec![false; vectors.len()];
+    let mut duplicates = vec![];
+    for positions in groups.values() {
+        // `min_by_key` and `max_by_key` return the first and the last extreme, respectively.
+        let representative = match policy {
+            DedupPolicy::KeepFirst | DedupPolicy::Off => positions[0],
+            DedupPolicy::KeepSmallest => *positions.iter().min_by_key(|p| blob_len(p)).unwrap(),
+            DedupPolicy::KeepLargest => {
+                *positions.iter().rev().max_by_key(|p| blob_len(p)).unwrap()
+            }
+        };
+        kept[representative] = true;
+        if positions.len() > 1 {
+            duplicates.push(DuplicateGroup {
+                kept: vectors[representative].name().to_string(),
+                dropped: positions
+                    .iter()
+                    .filter(|position| **position != representative)
+                    .map(|position| vectors[*position].name().to_string())
+                    .collect(),
+            });
+        }
+    }
+
+    let vectors = vectors
+        .into_iter()
+        .zip(kept)
+        .filter_map(|(vector, kept)| if kept { Some(vector) } else { None })
+        .collect();
+    (vectors, duplicates)
+}
+
+/// Prints the summary of dropped duplicates to stderr, so that it doesn't mix with the test vectors.
+pub(crate) fn report(kept_count: usize, duplicates: &[DuplicateGroup]) {
+    if duplicates.is_empty() {
+        return;
+    }
+    let dropped_count: usize = duplicates.iter().map(|group| group.dropped.len()).sum();
+    eprintln!(
+        "Deduplication kept {} vector(s), dropped {} duplicate(s) of {} vector(s):",
+        kept_count,
+        dropped_count,
+        duplicates.len()
+    );
+    for group in duplicates {
+        eprintln!(
+            "  {} - dropped {}: {}",
+            group.kept,
+            group.dropped.len(),
+            group.dropped.join(", ")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+    use casper_node::types::Deploy;
+    use casper_types::{
+        bytesrepr::Bytes, runtime_args, RuntimeArgs, SecretKey, TimeDiff, Timestamp, U512,
+    };
+
+    use crate::{
+        ledger::{self, LimitedLedgerConfig},
+        parser::config::ParserConfig,
+        sample::Sample,
+    };
+
+    use super::{deduplicate, DedupPolicy};
+
+    fn transfer_sample(label: &str, ttl: &str, key: SecretKey, amount: u64) -> Sample<Deploy> {
+        let payment = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: runtime_args! { "amount" => U512::from(2_500_000_000u64) },
+        };
+        let session = ExecutableDeployItem::Transfer {
+            args: runtime_args! {
+                "amount" => U512::from(amount),
+                "id" => Some(1u64),
+                "target" => [1u8; 32]
+            },
+        };
+        let deploy = Deploy::new(
+            Timestamp::from_str("2021-05-04T14:20:35.104Z").unwrap(),
+            TimeDiff::from_str(ttl).unwrap(),
+            1,
+            vec![],
+            "casper-test".to_string(),
+            payment,
+            session,
+            &key,
+            None,
+        );
+        Sample::new(label, deploy, true)
+    }
+
+    #[test]
+    fn deploys_differing_in_ttl_or_keys_are_merged() {
+        let samples = vec![
+            transfer_sample(
+                "first",
+                "1h",
+                SecretKey::ed25519_from_bytes([1; 32]).unwrap(),
+                100,
+            ),
+            transfer_sample(
+                "ttl",
+                "1day",
+                SecretKey::ed25519_from_bytes([1; 32]).unwrap(),
+                100,
+            ),
+            transfer_sample(
+                "key",
+                "1h",
+                SecretKey::secp256k1_from_bytes([2; 32]).unwrap(),
+                100,
+            ),
+            transfer_sample(
+                "amount",
+                "1h",
+                SecretKey::ed25519_from_bytes([1; 32]).unwrap(),
+                200,
+            ),
+        ];
+        let vectors = samples
+            .into_iter()
+            .enumerate()
+            .map(|(index, sample)| {
+                ledger::deploy_to_json(
+                    index,
+                    sample,
+                    &LimitedLedgerConfig::new(15),
+                    &ParserConfig::default(),
+                )
+            })
+            .collect();
+
+        let (kept, duplicates) = deduplicate(vectors, DedupPolicy::KeepFirst);
+        let kept: Vec<&str> = kept.iter().map(|vector| vector.name()).collect();
+        assert_eq!(kept, vec!["first", "amount"]);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].dropped, vec!["ttl", "key"]);
+    }
+}
@@ -0,0 +1,169 @@
+//! Explains how a single test vector is generated and rendered, so that a failure reported
+//! on one vector can be investigated without searching through the whole `manual.json`.
+
+use std::fmt::Display;
+
+use crate::{
+    ledger::LimitedLedgerConfig, oracle::ValidityOracle, test_data::rng::StreamSeed,
+    vectors::TestVector,
+};
+
+/// Environment variable with the master seed of the test vectors.
+const TEST_SEED_ENV: &str = "CL_TEST_SEED";
+
+/// Why `explain` couldn't pick a single vector.
+#[derive(Debug, PartialEq)]
+pub(crate) enum LookupError {
+    /// No label starts with the query and there's no vector at that position.
+    NotFound(String),
+    /// The query is a prefix of several labels, none of them equal to it.
+    Ambiguous(String, Vec<String>),
+}
+
+impl Display for LookupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LookupError::NotFound(query) => {
+                write!(f, "No vector labeled {}... or at position {}", query, query)
+            }
+            LookupError::Ambiguous(query, candidates) => {
+                write!(f, "{} matches {} vectors:", query, candidates.len())?;
+                for candidate in candidates {
+                    write!(f, "\n  {}", candidate)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Prints the explanation of the vector labeled `query`, or of the only vector whose label
+/// starts with `query`, e.g. `redelegate__missing_validator__type_by_name__payment_system`
+/// without the random header suffix (`__ts_<millis>__gas_<price>__chain_<kind>`), or of
+/// the vector at the `query` position of the current `manual.json`. Positions shift whenever
+/// vectors are added, so they're only good for looking up a vector of the same generation.
+pub(crate) fn explain(
+    vectors: &[TestVector],
+    query: &str,
+    streams: &StreamSeed,
+    ledger_config: &LimitedLedgerConfig,
+    oracle: &ValidityOracle,
+) -> Result<(), LookupError> {
+    let index = lookup(vectors.iter().map(TestVector::label), query)?;
+    explain_vector(index, &vectors[index], streams, ledger_config, oracle);
+    Ok(())
+}
+
+/// Position of the vector `query` refers to: the one labeled exactly `query`, the one
+/// at the `query` position, or the only one whose label starts with `query` - in that order.
+fn lookup<'a>(labels: impl Iterator<Item = &'a str>, query: &str) -> Result<usize, LookupError> {
+    let labels: Vec<&str> = labels.collect();
+    if let Some(index) = labels.iter().position(|label| *label == query) {
+        return Ok(index);
+    }
+    if let Some(index) = query
+        .parse::<usize>()
+        .ok()
+        .filter(|idx| *idx < labels.len())
+    {
+        return Ok(index);
+    }
+    let matching: Vec<usize> = (0..labels.len())
+        .filter(|idx| labels[*idx].starts_with(query))
+        .collect();
+    match matching.as_slice() {
+        [] => Err(LookupError::NotFound(query.to_string())),
+        [index] => Ok(*index),
+        _ => Err(LookupError::Ambiguous(
+            query.to_string(),
+            matching
+                .iter()
+                .map(|idx| labels[*idx].to_string())
+                .collect(),
+        )),
+    }
+}
+
+fn explain_vector(
+    index: usize,
+    vector: &TestVector,
+    streams: &StreamSeed,
+    ledger_config: &LimitedLedgerConfig,
+    oracle: &ValidityOracle,
+) {
+    println!("=== {} (position {} in manual.json)", vector.label(), index);
+
+    println!("--- Reproduce");
+    match std::env::var(TEST_SEED_ENV) {
+        Ok(seed) => println!("{}={}", TEST_SEED_ENV, seed),
+        Err(_) => println!("{} not set - random seed", TEST_SEED_ENV),
+    }
+    println!("streams seed: {}", streams);
+    println!("category stream: {}", vector.category());
+    match vector.deploy().and_then(|sample| sample.stream()) {
+        Some(stream) => println!("sample stream: {}", stream),
+        None => println!("sample stream: none - drawn from the category stream"),
+    }
+
+    if let Some(sample) = vector.deploy() {
+        println!("--- Deploy");
+        println!(
+            "{}",
+            serde_json::to_string_pretty(sample.sample()).expect("deploy should serialize to JSON")
+        );
+        let verdict = match oracle.validate(sample.sample()) {
+            Ok(()) => "valid".to_string(),
+            Err(reason) => format!("invalid ({})", reason),
+        };
+        println!("flagged valid: {}, oracle: {}", sample.valid(), verdict);
+    }
+
+    println!("--- Elements");
+    for element in vector.elements() {
+        let mode = if element.is_expert() {
+            "expert"
+        } else {
+            "regular"
+        };
+        println!("{:<7} | {}", mode, element);
+    }
+
+    println!("--- Vector");
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&vector.to_json(index, ledger_config)).unwrap()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lookup, LookupError};
+
+    const LABELS: [&str; 3] = [
+        "native_transfer__ts_1__gas_1__chain_chainspec",
+        "native_transfer__ts_1__gas_1__chain_chainspec__options",
+        "delegate__ts_1__gas_1__chain_chainspec",
+    ];
+
+    #[test]
+    fn exact_label_wins_over_prefix() {
+        assert_eq!(lookup(LABELS.iter().copied(), LABELS[0]), Ok(0));
+        assert_eq!(lookup(LABELS.iter().copied(), "delegate"), Ok(2));
+        assert_eq!(lookup(LABELS.iter().copied(), "1"), Ok(1));
+    }
+
+    #[test]
+    fn ambiguous_prefix_lists_candidates() {
+        assert_eq!(
+            lookup(LABELS.iter().copied(), "native_transfer"),
+            Err(LookupError::Ambiguous(
+                "native_transfer".to_string(),
+                vec![LABELS[0].to_string(), LABELS[1].to_string()]
+            ))
+        );
+        assert_eq!(
+            lookup(LABELS.iter().copied(), "undelegate"),
+            Err(LookupError::NotFound("undelegate".to_string()))
+        );
+    }
+}
@@ -5,14 +5,18 @@ use casper_types::bytesrepr::ToBytes;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{message::CasperMessage, parser, sample::Sample};
+use crate::{
+    message::CasperMessage,
+    parser::{self, config::ParserConfig},
+    sample::Sample,
+};
 
 // Character limit for Ledger's "label" row.
 const LEDGER_VIEW_NAME_CHAR_COUNT: usize = 11;
 // Character limit for Ledger's value top row.
-const LEDGER_VIEW_TOP_ROW_CHAR_COUNT: usize = 17;
+pub(crate) const LEDGER_VIEW_TOP_ROW_CHAR_COUNT: usize = 17;
 // Character limit for Ledger's value bottom row.
-const LEDGER_VIEW_BOTTOM_CHAR_COUNT: usize = 17;
+pub(crate) const LEDGER_VIEW_BOTTOM_CHAR_COUNT: usize = 17;
 
 #[derive(Clone, Copy)]
 pub(crate) enum TxnPhase {
@@ -55,21 +59,64 @@ fn capitalize_first(s: &str) -> String {
     }
 }
 
+// Whether the device can display the character: it only has glyphs of the printable ASCII characters.
+fn is_displayable(c: char) -> bool {
+    matches!(c, ' '..='~')
+}
+
+// Escapes the characters the device can't display, so that they're visible instead of rendering
+// unpredictably (or not at all, like the zero-width space or the right-to-left override):
+// `\n`, `\r` and `\t` for the common whitespace, `\u{XXXX}` for anything else.
+// The backslash itself is escaped as well, so that an escape can't be faked with plain ASCII.
+fn sanitize(value: String) -> String {
+    if value.chars().all(|c| is_displayable(c) && c != '\\') {
+        return value;
+    }
+    value
+        .chars()
+        .map(|c| match c {
+            '\\' => "\\\\".to_string(),
+            c if is_displayable(c) => c.to_string(),
+            c => c.escape_default().to_string(),
+        })
+        .collect()
+}
+
+// Splits the sanitized value into the units that are displayed together: single characters
+// and escape sequences, which shouldn't be split between rows.
+fn display_units(value: &str) -> Vec<&str> {
+    let mut units = vec![];
+    let mut rest = value;
+    while let Some(c) = rest.chars().next() {
+        let len = match rest.strip_prefix("\\u{") {
+            Some(escaped) => "\\u{".len() + escaped.find('}').map_or(escaped.len(), |end| end + 1),
+            None if c == '\\' => rest.chars().take(2).map(char::len_utf8).sum(),
+            None => c.len_utf8(),
+        };
+        let (unit, remainder) = rest.split_at(len);
+        units.push(unit);
+        rest = remainder;
+    }
+    units
+}
+
 impl Element {
     /// Creates an instance of the element, marking it as to be displayed in expert-only mode.
+    /// Characters the device can't display are escaped.
     pub(crate) fn expert(name: &str, value: String) -> Element {
         Element {
             name: capitalize_first(name),
-            value,
+            value: sanitize(value),
             expert: true,
         }
     }
 
     /// Creates an instance of the element, marking it as to be displayed in regular mode.
+    /// Characters the device can't display are escaped.
     pub(crate) fn regular(name: &str, value: String) -> Self {
         Element {
             name: capitalize_first(name),
-            value,
+            value: sanitize(value),
             expert: false,
         }
     }
@@ -78,6 +125,25 @@ impl Element {
     pub(crate) fn as_expert(&mut self) {
         self.expert = true;
     }
+
+    /// Whether the element is displayed in expert mode only.
+    pub(crate) fn is_expert(&self) -> bool {
+        self.expert
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl Display for Element {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} : {}", self.name, self.value)
+    }
 }
 
 #[derive(Clone)]
@@ -87,9 +153,9 @@ struct Ledger {
 }
 
 impl Ledger {
-    fn from_deploy(deploy: Deploy) -> Self {
+    fn from_deploy(deploy: Deploy, parser_config: &ParserConfig) -> Self {
         Ledger {
-            ledger_elements: parser::parse_deploy(deploy),
+            ledger_elements: parser::parse_deploy(deploy, parser_config),
         }
     }
 
@@ -111,19 +177,22 @@ struct LedgerValue {
 }
 
 impl LedgerValue {
-    // Adds a char to the ledger value.
+    // Adds a display unit (a char or an escape sequence) to the ledger value.
     // Single value is limited by the number of chars that can be
     // printed on one ledger view: 34 char total in two lines.
-    // Function first tries to add a new char to the top row, if that is full
-    // then tries to add it to the bottom row.
-    // Returns whether adding char was successful.
-    fn add_char(&mut self, c: char) -> bool {
-        if self.top.chars().count() < LEDGER_VIEW_TOP_ROW_CHAR_COUNT {
-            self.top = format!("{}{}", self.top, c);
+    // Function first tries to add a new unit to the top row, if that is full
+    // then tries to add it to the bottom row. A unit is never split between the rows.
+    // Returns whether adding the unit was successful.
+    fn add_unit(&mut self, unit: &str) -> bool {
+        let width = unit.chars().count();
+        if self.bottom.is_empty()
+            && self.top.chars().count() + width <= LEDGER_VIEW_TOP_ROW_CHAR_COUNT
+        {
+            self.top.push_str(unit);
             return true;
         }
-        if self.bottom.chars().count() < LEDGER_VIEW_BOTTOM_CHAR_COUNT {
-            self.bottom = format!("{}{}", self.bottom, c);
+        if self.bottom.chars().count() + width <= LEDGER_VIEW_BOTTOM_CHAR_COUNT {
+            self.bottom.push_str(unit);
             return true;
         }
         false
@@ -169,14 +238,14 @@ impl LedgerPageView {
         }
         let mut values = vec![];
         let mut curr_value = LedgerValue::default();
-        for c in element.value.chars() {
-            let added = curr_value.add_char(c);
+        for unit in display_units(&element.value) {
+            let added = curr_value.add_unit(unit);
             if !added {
                 // Single ledger page can't contain more characters.
                 values.push(curr_value.clone());
                 // Create new Ledger page for that element.
                 curr_value = LedgerValue::default();
-                assert!(curr_value.add_char(c));
+                assert!(curr_value.add_unit(unit));
             }
         }
         // Add the last view to the collection.
@@ -189,6 +258,31 @@ impl LedgerPageView {
         }
     }
 
+    /// Checks that the page fits on the device: its label and both rows of every one of its values.
+    fn check_fits(&self) -> Result<(), String> {
+        if self.name.chars().count() > LEDGER_VIEW_NAME_CHAR_COUNT {
+            return Err(format!(
+                "label {} longer than {} characters",
+                self.name, LEDGER_VIEW_NAME_CHAR_COUNT
+            ));
+        }
+        for value in &self.values {
+            if value.top.chars().count() > LEDGER_VIEW_TOP_ROW_CHAR_COUNT
+                || value.bottom.chars().count() > LEDGER_VIEW_BOTTOM_CHAR_COUNT
+            {
+                return Err(format!(
+                    "{} value {} / {} longer than {} / {} characters",
+                    self.name,
+                    value.top,
+                    value.bottom,
+                    LEDGER_VIEW_TOP_ROW_CHAR_COUNT,
+                    LEDGER_VIEW_BOTTOM_CHAR_COUNT
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// Turn the current element into printable Ledger views.
     /// Adds indexes and labels.
     fn to_string(&self) -> Vec<String> {
@@ -316,15 +410,49 @@ pub(super) struct ZondaxRepr {
     output_expert: Vec<String>,
 }
 
+impl ZondaxRepr {
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Hex-encoded bytes of the deploy or message.
+    pub(crate) fn blob(&self) -> &str {
+        &self.blob
+    }
+
+    /// Validity in regular and expert mode, respectively.
+    pub(crate) fn validity(&self) -> (bool, bool) {
+        (self.valid_regular, self.valid_expert)
+    }
+
+    /// Pages of regular and expert mode, respectively.
+    pub(crate) fn outputs(&self) -> (&[String], &[String]) {
+        (&self.output, &self.output_expert)
+    }
+}
+
+/// Splits the deploy into Ledger pages, the way its test vector is rendered, and checks that every one of them
+/// fits on the device. Panics when the deploy can't be rendered at all.
+pub(crate) fn check_deploy_pages(
+    deploy: Deploy,
+    parser_config: &ParserConfig,
+) -> Result<(), String> {
+    LedgerView::from_ledger(Ledger::from_deploy(deploy, parser_config))
+        .pages
+        .iter()
+        .try_for_each(LedgerPageView::check_fits)
+}
+
 /// Maps `Deploy` structure to the expected JSON representation.
 pub(super) fn deploy_to_json(
     index: usize,
     sample_deploy: Sample<Deploy>,
     config: &LimitedLedgerConfig,
+    parser_config: &ParserConfig,
 ) -> ZondaxRepr {
     let (name, deploy, valid) = sample_deploy.destructure();
     let blob = hex::encode(deploy.to_bytes().unwrap());
-    let ledger = Ledger::from_deploy(deploy);
+    let ledger = Ledger::from_deploy(deploy, parser_config);
     let ledger_view = LimitedLedgerView::new(config, ledger);
     let output = ledger_view.regular();
     let output_expert = ledger_view.expert();
@@ -365,3 +493,52 @@ pub(super) fn message_to_json(
         output_expert,
     }
 }
+
+#[cfg(test)]
+mod sanitization {
+    use super::{Element, LedgerPageView, LedgerValue};
+
+    fn pages(value: &str) -> Vec<String> {
+        LedgerPageView::from_element(Element::regular("name", value.to_string())).to_string()
+    }
+
+    #[test]
+    fn non_displayable_chars_are_escaped() {
+        let element = Element::regular("name", "Token\u{200B}transfer\n".to_string());
+        assert_eq!(element.value(), "Token\\u{200b}transfer\\n");
+        // The backslash is escaped, so the escapes can't be faked with ASCII.
+        let element = Element::regular("name", "\\u{200b}".to_string());
+        assert_eq!(element.value(), "\\\\u{200b}");
+        let element = Element::regular("name", "casper-\u{e9}\u{1F600}".to_string());
+        assert_eq!(element.value(), "casper-\\u{e9}\\u{1f600}");
+    }
+
+    #[test]
+    fn escapes_are_not_split_between_rows() {
+        // The escape doesn't fit the 17th character of the top row, so it starts the bottom one.
+        assert_eq!(
+            pages(&format!("{}\u{202E}", "a".repeat(16))),
+            vec![format!("Name : {}\\u{{202e}}", "a".repeat(16))]
+        );
+        // Nor between pages.
+        assert_eq!(
+            pages(&format!("{}\u{202E}", "a".repeat(30))),
+            vec![
+                format!("Name [1/2] : {}", "a".repeat(30)),
+                "Name [2/2] : \\u{202e}".to_string(),
+            ]
+        );
+    }
+    #[test]
+    fn overlong_rows_do_not_fit() {
+        let page = LedgerPageView::from_element(Element::regular("name", "a".repeat(100)));
+        assert_eq!(page.check_fits(), Ok(()));
+
+        let mut page = page;
+        page.values.push(LedgerValue {
+            top: "a".repeat(18),
+            bottom: String::new(),
+        });
+        assert!(page.check_fits().is_err());
+    }
+}
@@ -1,55 +1,111 @@
 use casper_types::testing::TestRng;
+use chainspec::ChainspecLimits;
+use coverage::CoverageMatrix;
+use dedup::DedupPolicy;
 use ledger::{LimitedLedgerConfig, ZondaxRepr};
-use test_data::{
-    delegate_samples, generic_samples, native_transfer_samples, redelegate_samples,
-    undelegate_samples,
-};
+use oracle::ValidityOracle;
+use parser::config::ParserConfig;
 
-use crate::test_data::sign_message::{invalid_casper_message_sample, valid_casper_message_sample};
+use crate::test_data::{
+    combinations::{self, Combination},
+    rng::StreamSeed,
+};
 
+mod chainspec;
 pub mod checksummed_hex;
+mod coverage;
+mod dedup;
+mod explain;
 mod ledger;
 mod message;
+mod oracle;
 mod parser;
 mod sample;
 mod test_data;
 mod utils;
+mod vectors;
 
 fn main() {
     let mut rng = TestRng::new();
+    // Each category of samples draws from its own stream derived from the master seed,
+    // so that changes in one category don't affect the others.
+    let streams = StreamSeed::new(&mut rng);
 
     let page_limit = 15;
 
     let limited_ledger_config = LimitedLedgerConfig::new(page_limit);
 
-    let mut id = 0;
-    let mut data: Vec<ZondaxRepr> = vec![];
+    // The original representation - vectors of the optional parts are rendered with their own config.
+    let parser_config = ParserConfig::default();
+
+    // Limits of the target network, see `CL_CHAINSPEC`.
+    let chainspec = ChainspecLimits::load();
+
+    let validity_oracle = ValidityOracle::new(&chainspec);
+
+    // How values of sample dimensions are combined, see `CL_COMBINATION`.
+    let combination = Combination::from_env();
+
+    // Generic samples nest composite args up to this depth, e.g. `List<Option<U512>>` is of depth 2.
+    let composite_args_depth = 2;
 
-    for sample_deploy in undelegate_samples(&mut rng)
-        .into_iter()
-        .chain(delegate_samples(&mut rng))
-        .chain(native_transfer_samples(&mut rng))
-        .chain(redelegate_samples(&mut rng))
-        .chain(generic_samples(&mut rng))
-    {
-        data.push(ledger::deploy_to_json(
-            id,
-            sample_deploy,
-            &limited_ledger_config,
-        ));
-        id += 1;
+    let (vectors, coverage) = vectors::all(
+        &streams,
+        &chainspec,
+        &parser_config,
+        combination,
+        composite_args_depth,
+    );
+
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        // `explain <label|label prefix|position>` prints how the matching vector is generated and rendered,
+        // instead of the whole `manual.json`.
+        Some("explain") => {
+            let query = args
+                .next()
+                .expect("explain requires a vector label prefix or position");
+            if let Err(error) = explain::explain(
+                &vectors,
+                &query,
+                &streams,
+                &limited_ledger_config,
+                &validity_oracle,
+            ) {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            }
+            return;
+        }
+        // `coverage` prints which combinations of item variants, phases, types, validity
+        // and key algorithms are covered by the vectors, and which are missing.
+        Some("coverage") => {
+            print!("{}", CoverageMatrix::new(&vectors));
+            return;
+        }
+        Some(command) => panic!("unknown command: {}", command),
+        None => {}
+    }
+
+    let mut mismatches = vec![];
+    let mut data: Vec<ZondaxRepr> = vec![];
+    for (id, vector) in vectors.iter().enumerate() {
+        if let Some(sample_deploy) = vector.deploy() {
+            mismatches.extend(validity_oracle.check(sample_deploy));
+        }
+        data.push(vector.to_json(id, &limited_ledger_config));
     }
 
-    for sample_casper_message in valid_casper_message_sample()
-        .into_iter()
-        .chain(invalid_casper_message_sample())
-    {
-        data.push(ledger::message_to_json(
-            id,
-            sample_casper_message,
-            &limited_ledger_config,
-        ));
-        id += 1;
+    // Duplicates are dropped according to `CL_DEDUP`.
+    let (data, duplicates) = dedup::deduplicate(data, DedupPolicy::from_env());
+
+    combinations::report(&coverage);
+    dedup::report(data.len(), &duplicates);
+
+    // Mislabeled vectors would make the app tests fail for the wrong reason - none are shipped.
+    if !mismatches.is_empty() {
+        oracle::report(&mismatches);
+        std::process::exit(1);
     }
 
     println!("{}", serde_json::to_string_pretty(&data).unwrap());
@@ -4,6 +4,7 @@ use casper_types::{blake2b, BLAKE2B_DIGEST_LENGTH};
 /// are prepended with the following prefix.
 const MSG_PREFIX: &str = "Casper Message:\n";
 
+#[derive(Clone)]
 pub(crate) struct CasperMessage(Vec<u8>);
 
 impl CasperMessage {
@@ -29,8 +30,43 @@ impl CasperMessage {
         &self.0
     }
 
+    /// Returns the message body - the bytes after `MSG_PREFIX` - unless the prefix is missing.
+    pub(crate) fn body(&self) -> Option<&[u8]> {
+        self.0.strip_prefix(MSG_PREFIX.as_bytes())
+    }
+
+    /// Returns the message body as text, if it's valid UTF-8 without control characters
+    /// other than line breaks and tabs. Empty and binary bodies have no text.
+    pub(crate) fn text(&self) -> Option<&str> {
+        let text = std::str::from_utf8(self.body()?).ok()?;
+        let printable = text
+            .chars()
+            .all(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t'));
+        if text.is_empty() || !printable {
+            return None;
+        }
+        Some(text)
+    }
+
     /// Returns blake2b hash of the underlying bytes.
     pub(crate) fn hashed(&self) -> [u8; BLAKE2B_DIGEST_LENGTH] {
         blake2b(&self.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::CasperMessage;
+
+    #[test]
+    fn text_is_printable_body() {
+        let message = CasperMessage::new("Hello,\nCasper \u{1F44B}".as_bytes().to_vec());
+        assert_eq!(message.text(), Some("Hello,\nCasper \u{1F44B}"));
+
+        // Missing prefix, binary body, control characters and an empty body.
+        assert_eq!(CasperMessage::raw(b"Casper:Hello".to_vec()).text(), None);
+        assert_eq!(CasperMessage::new(vec![0xff, 0xfe, 0x00]).text(), None);
+        assert_eq!(CasperMessage::new(b"Hello\x07".to_vec()).text(), None);
+        assert_eq!(CasperMessage::new(vec![]).text(), None);
+    }
+}
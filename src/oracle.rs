@@ -0,0 +1,189 @@
+//! Validity oracle - cross-checks validity flags of the samples with the checks
+//! a casper-node runs on every deploy it receives, so that mislabeled samples are caught
+//! before the test vectors are shipped.
+
+use std::fmt::Display;
+
+use casper_node::types::{Deploy, DeployConfig, DeployConfigurationFailure};
+use casper_types::TimeDiff;
+
+use crate::{
+    chainspec::ChainspecLimits,
+    ledger::TxnPhase,
+    parser::validation::{
+        validate_header, validate_payment, validate_placement, validate_session, ArgError,
+        HeaderError, PlacementError,
+    },
+    sample::Sample,
+    test_data::sample_chain_names,
+};
+
+// Deploys are checked at the time of their creation, so that the leeway for timestamps
+// from the future doesn't matter.
+const TIMESTAMP_LEEWAY: TimeDiff = TimeDiff::from_seconds(0);
+
+/// Reason why the oracle considers a deploy invalid.
+#[derive(Debug)]
+pub(crate) enum Invalidity {
+    /// Deploy failed casper-node's own offline checks.
+    Node(DeployConfigurationFailure),
+    /// Header makes the deploy impossible to execute.
+    Header(HeaderError),
+    /// Payment doesn't follow the type rules of the well-known arguments.
+    Payment(ArgError),
+    /// Session doesn't follow the type rules of the well-known arguments.
+    Session(ArgError),
+    /// Payment or session item can't be executed in its phase.
+    Placement(PlacementError),
+}
+
+impl Display for Invalidity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Invalidity::Node(failure) => write!(f, "node: {}", failure),
+            Invalidity::Header(error) => write!(f, "header: {}", error),
+            Invalidity::Payment(error) => write!(f, "payment: {}", error),
+            Invalidity::Session(error) => write!(f, "session: {}", error),
+            Invalidity::Placement(error) => write!(f, "placement: {}", error),
+        }
+    }
+}
+
+/// Sample whose validity flag disagrees with the oracle.
+pub(crate) struct Mismatch {
+    label: String,
+    flagged_valid: bool,
+    reason: Option<Invalidity>,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.reason {
+            Some(reason) if self.flagged_valid => {
+                write!(f, "{}: flagged valid but {}", self.label, reason)
+            }
+            _ => write!(f, "{}: flagged invalid but passes all checks", self.label),
+        }
+    }
+}
+
+pub(crate) struct ValidityOracle {
+    /// Names of the networks the deploys may be meant for, the chainspec's one first.
+    chain_names: Vec<String>,
+    deploy_config: DeployConfig,
+    max_associated_keys: u32,
+}
+
+impl ValidityOracle {
+    /// Creates the oracle checking deploys against the limits of the chainspec.
+    pub(crate) fn new(chainspec: &ChainspecLimits) -> Self {
+        ValidityOracle {
+            chain_names: sample_chain_names(chainspec)
+                .iter()
+                .map(|(_, chain_name)| chain_name.clone())
+                .collect(),
+            deploy_config: chainspec.deploy_config().clone(),
+            max_associated_keys: chainspec.max_associated_keys(),
+        }
+    }
+
+    /// Runs the deploy through casper-node's offline validation
+    /// (hashes and signatures, compliance with the chainspec) and our own header and type rules.
+    ///
+    /// Our own checks of the chainspec limits (`ChainspecLimits::check`) are left out - casper-node's
+    /// take their place, so that a sample mislabeled by ours is caught.
+    ///
+    /// Ledger can't tell which network a deploy is meant for, so deploys named after any of the networks
+    /// the samples are rendered with are checked as if they were meant for it. Any other chain name is invalid.
+    pub(crate) fn validate(&self, deploy: &Deploy) -> Result<(), Invalidity> {
+        deploy.clone().is_valid().map_err(Invalidity::Node)?;
+        deploy
+            .is_config_compliant(
+                self.network_of(deploy),
+                &self.deploy_config,
+                self.max_associated_keys,
+                TIMESTAMP_LEEWAY,
+                deploy.header().timestamp(),
+            )
+            .map_err(Invalidity::Node)?;
+        validate_header(deploy.header(), deploy.payment()).map_err(Invalidity::Header)?;
+        validate_placement(TxnPhase::Payment, deploy.payment()).map_err(Invalidity::Placement)?;
+        validate_placement(TxnPhase::Session, deploy.session()).map_err(Invalidity::Placement)?;
+        validate_payment(deploy.payment()).map_err(Invalidity::Payment)?;
+        validate_session(deploy.session()).map_err(Invalidity::Session)
+    }
+
+    /// Chain name of the network the deploy is checked against - its own, if it's one of the known networks,
+    /// the chainspec's otherwise.
+    fn network_of(&self, deploy: &Deploy) -> &str {
+        self.chain_names
+            .iter()
+            .find(|chain_name| chain_name.as_str() == deploy.header().chain_name())
+            .unwrap_or(&self.chain_names[0])
+    }
+
+    /// Returns `Some` when the validity flag of the sample disagrees with the oracle.
+    pub(crate) fn check(&self, sample: &Sample<Deploy>) -> Option<Mismatch> {
+        let result = self.validate(sample.sample());
+        if result.is_ok() == sample.valid() {
+            return None;
+        }
+        Some(Mismatch {
+            label: sample.label().to_string(),
+            flagged_valid: sample.valid(),
+            reason: result.err(),
+        })
+    }
+}
+
+/// Prints all the mismatches to stderr, so that they don't mix with the test vectors.
+pub(crate) fn report(mismatches: &[Mismatch]) {
+    if mismatches.is_empty() {
+        return;
+    }
+    eprintln!(
+        "ERROR: {} sample(s) have validity flags that disagree with the validity oracle:",
+        mismatches.len()
+    );
+    for mismatch in mismatches {
+        eprintln!("  {}", mismatch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use crate::{
+        chainspec::ChainspecLimits,
+        parser::config::ParserConfig,
+        test_data::{
+            combinations::Combination,
+            rng::{StreamRng, StreamSeed},
+        },
+        vectors,
+    };
+
+    use super::ValidityOracle;
+
+    #[test]
+    fn every_sample_agrees_with_the_oracle() {
+        let chainspec = ChainspecLimits::default();
+        let streams = StreamSeed::new(&mut StreamRng::seed_from_u64(0));
+        let (vectors, _) = vectors::all(
+            &streams,
+            &chainspec,
+            &ParserConfig::default(),
+            Combination::Exhaustive,
+            2,
+        );
+        let oracle = ValidityOracle::new(&chainspec);
+        let mismatches: Vec<String> = vectors
+            .iter()
+            .filter_map(|vector| vector.deploy())
+            .filter_map(|sample| oracle.check(sample))
+            .map(|mismatch| mismatch.to_string())
+            .collect();
+        assert!(mismatches.is_empty(), "{:#?}", mismatches);
+    }
+}
@@ -1,7 +1,10 @@
 mod auction;
+pub(crate) mod config;
+mod confusables;
 mod deploy;
 mod runtime_args;
 mod utils;
+pub(crate) mod validation;
 
 use casper_node::types::Deploy;
 
@@ -9,24 +12,36 @@ use crate::{
     checksummed_hex,
     ledger::{Element, TxnPhase},
     message::CasperMessage,
-    parser::deploy::{parse_approvals, parse_deploy_header, parse_phase},
+    parser::{
+        config::ParserConfig,
+        deploy::{parse_approvals, parse_deploy_header, parse_phase},
+    },
 };
 
+/// Displays the text of the message, with its hash in expert mode.
+/// Messages that aren't printable text (or lack the prefix) are displayed as their hash only.
 pub(crate) fn parse_message(m: CasperMessage) -> Vec<Element> {
-    vec![Element::regular("Msg hash", hex::encode(m.hashed()))]
+    let hash = hex::encode(m.hashed());
+    match m.text() {
+        Some(text) => vec![
+            Element::regular("Message", text.to_string()),
+            Element::expert("Msg hash", hash),
+        ],
+        None => vec![Element::regular("Msg hash", hash)],
+    }
 }
 
-pub(crate) fn parse_deploy(d: Deploy) -> Vec<Element> {
+pub(crate) fn parse_deploy(d: Deploy, config: &ParserConfig) -> Vec<Element> {
     let mut elements = vec![];
     elements.push(Element::regular(
         "Txn hash",
         checksummed_hex::encode(d.hash().inner()).to_string(),
     ));
     elements.push(deploy_type(&d));
-    elements.extend(parse_deploy_header(d.header()));
-    elements.extend(parse_phase(d.payment(), TxnPhase::Payment));
-    elements.extend(parse_phase(d.session(), TxnPhase::Session));
-    elements.extend(parse_approvals(&d));
+    elements.extend(parse_deploy_header(d.header(), config));
+    elements.extend(parse_phase(d.payment(), TxnPhase::Payment, config));
+    elements.extend(parse_phase(d.session(), TxnPhase::Session, config));
+    elements.extend(parse_approvals(&d, config));
     elements
 }
 
@@ -44,3 +59,33 @@ fn deploy_type(d: &Deploy) -> Element {
     };
     Element::regular("Type", dtype.to_string())
 }
+
+#[cfg(test)]
+mod message {
+    use crate::message::CasperMessage;
+
+    use super::parse_message;
+
+    fn pages(message: CasperMessage) -> Vec<(String, bool)> {
+        parse_message(message)
+            .iter()
+            .map(|element| (element.name().to_string(), element.is_expert()))
+            .collect()
+    }
+
+    #[test]
+    fn text_is_displayed_with_hash_in_expert_mode() {
+        let text = CasperMessage::new(b"Sign in\nNonce: 1".to_vec());
+        assert_eq!(
+            pages(text.clone()),
+            vec![
+                ("Message".to_string(), false),
+                ("Msg hash".to_string(), true)
+            ]
+        );
+        assert_eq!(parse_message(text)[0].value(), "Sign in\\nNonce: 1");
+
+        let binary = CasperMessage::new(vec![0xff, 0x00]);
+        assert_eq!(pages(binary), vec![("Msg hash".to_string(), false)]);
+    }
+}
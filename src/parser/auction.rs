@@ -1,12 +1,18 @@
 use casper_execution_engine::core::engine_state::ExecutableDeployItem;
-use casper_types::{system::mint, RuntimeArgs};
+use casper_types::RuntimeArgs;
 
 use crate::{
     ledger::{Element, TxnPhase},
-    parser::deploy::{deploy_type, parse_amount},
+    parser::{
+        config::ParserConfig,
+        deploy::{deploy_type, parse_amount},
+    },
 };
 
-use super::{deploy::identity, runtime_args::parse_optional_arg};
+use super::{
+    runtime_args::parse_public_key_arg,
+    validation::{validate_delegation_args, validate_redelegation_args},
+};
 
 fn parse_auction_item<'a, F>(
     method: &str,
@@ -41,66 +47,75 @@ where
     elements
 }
 
-pub(crate) fn parse_delegation(item: &ExecutableDeployItem) -> Vec<Element> {
+pub(crate) fn parse_delegation(item: &ExecutableDeployItem, config: &ParserConfig) -> Vec<Element> {
     let arg_parser = |args| {
         let mut elements = vec![];
         // Public key of the account we're delegating from.
-        elements.extend(parse_delegator(args));
+        elements.extend(parse_delegator(args, config));
         // Public key of the validator we're delegating to.
-        elements.extend(parse_validator(args));
+        elements.extend(parse_validator(args, config));
         // Amount we're delegating.
-        elements.extend(parse_amount(args));
+        elements.extend(parse_amount(args, config));
         elements
     };
     parse_auction_item("delegate", item, arg_parser)
 }
 
-pub(crate) fn parse_undelegation(item: &ExecutableDeployItem) -> Vec<Element> {
+pub(crate) fn parse_undelegation(
+    item: &ExecutableDeployItem,
+    config: &ParserConfig,
+) -> Vec<Element> {
     let arg_parser = |args| {
         let mut elements = vec![];
         // Public key of the account we're delegating from.
-        elements.extend(parse_delegator(args));
+        elements.extend(parse_delegator(args, config));
         // Public key of the validator we're delegating to.
-        elements.extend(parse_validator(args));
+        elements.extend(parse_validator(args, config));
         // Amount we're delegating.
-        elements.extend(parse_amount(args));
+        elements.extend(parse_amount(args, config));
         elements
     };
     parse_auction_item("undelegate", item, arg_parser)
 }
 
-pub(crate) fn parse_redelegation(item: &ExecutableDeployItem) -> Vec<Element> {
+pub(crate) fn parse_redelegation(
+    item: &ExecutableDeployItem,
+    config: &ParserConfig,
+) -> Vec<Element> {
     let arg_parser = |args| {
         let mut elements = vec![];
         // Public key of the account we're delegating from.
-        elements.extend(parse_delegator(args));
+        elements.extend(parse_delegator(args, config));
         // Public key of the current validator we have been redelagating to so far.
-        elements.extend(parse_old_validator(args));
+        elements.extend(parse_old_validator(args, config));
         // New validator we're redelegating to.
-        elements.extend(parse_new_validator(args));
+        elements.extend(parse_new_validator(args, config));
         // Amount we're delegating.
-        elements.extend(parse_amount(args));
+        elements.extend(parse_amount(args, config));
         elements
     };
     parse_auction_item("redelegate", item, arg_parser)
 }
 
 /// Returns `true` when the deploy's entry point is *literally* _delegate_
+/// and its arguments have the expected types.
 pub(crate) fn is_delegate(item: &ExecutableDeployItem) -> bool {
     (is_entrypoint(item, DELEGATE_ENTRYPOINT) || has_delegate_auction_arg(item))
-        && has_delegate_args(item)
+        && validate_delegation_args(item.args()).is_ok()
 }
 
 /// Returns `true` when the deploy's entry point is *literally* _undelegate_
+/// and its arguments have the expected types.
 pub(crate) fn is_undelegate(item: &ExecutableDeployItem) -> bool {
     (is_entrypoint(item, UNDELEGATE_ENTRYPOINT) || has_undelegate_auction_arg(item))
-        && has_undelegate_arg(item)
+        && validate_delegation_args(item.args()).is_ok()
 }
 
-/// Returns `true` when the deploy's entry point is *literally* _undelegate_
+/// Returns `true` when the deploy's entry point is *literally* _redelegate_
+/// and its arguments have the expected types.
 pub(crate) fn is_redelegate(item: &ExecutableDeployItem) -> bool {
     (is_entrypoint(item, REDELEGATE_ENTRYPOINT) || has_redelegate_auction_arg(item))
-        && has_redelegate_arg(item)
+        && validate_redelegation_args(item.args()).is_ok()
 }
 
 fn get_auction_arg(item: &ExecutableDeployItem) -> Option<String> {
@@ -119,10 +134,10 @@ fn get_auction_arg(item: &ExecutableDeployItem) -> Option<String> {
 
 const DELEGATE_ENTRYPOINT: &str = "delegate";
 const UNDELEGATE_ENTRYPOINT: &str = "undelegate";
-const REDELEGATE_ENTRYPOINT: &str = "redelegate";
-const DELEGATOR_ARG_KEY: &str = "delegator";
-const VALIDATOR_ARG_KEY: &str = "validator";
-const NEW_VALIDATOR_ARG_KEY: &str = "new_validator";
+pub(super) const REDELEGATE_ENTRYPOINT: &str = "redelegate";
+pub(super) const DELEGATOR_ARG_KEY: &str = "delegator";
+pub(super) const VALIDATOR_ARG_KEY: &str = "validator";
+pub(super) const NEW_VALIDATOR_ARG_KEY: &str = "new_validator";
 
 fn has_delegate_auction_arg(item: &ExecutableDeployItem) -> bool {
     get_auction_arg(item)
@@ -142,39 +157,20 @@ fn has_redelegate_auction_arg(item: &ExecutableDeployItem) -> bool {
         .is_some()
 }
 
-fn has_delegate_args(item: &ExecutableDeployItem) -> bool {
-    item.args().get(DELEGATOR_ARG_KEY).is_some()
-        && item.args().get(VALIDATOR_ARG_KEY).is_some()
-        && item.args().get(mint::ARG_AMOUNT).is_some()
-}
-
-fn has_undelegate_arg(item: &ExecutableDeployItem) -> bool {
-    item.args().get(DELEGATOR_ARG_KEY).is_some()
-        && item.args().get(VALIDATOR_ARG_KEY).is_some()
-        && item.args().get(mint::ARG_AMOUNT).is_some()
+fn parse_delegator(args: &RuntimeArgs, config: &ParserConfig) -> Vec<Element> {
+    parse_public_key_arg(args, DELEGATOR_ARG_KEY, "delegator", config)
 }
 
-fn has_redelegate_arg(item: &ExecutableDeployItem) -> bool {
-    item.args().get(DELEGATOR_ARG_KEY).is_some()
-        && item.args().get(VALIDATOR_ARG_KEY).is_some()
-        && item.args().get(NEW_VALIDATOR_ARG_KEY).is_some()
-        && item.args().get(mint::ARG_AMOUNT).is_some()
+fn parse_validator(args: &RuntimeArgs, config: &ParserConfig) -> Vec<Element> {
+    parse_public_key_arg(args, VALIDATOR_ARG_KEY, "validator", config)
 }
 
-fn parse_delegator(args: &RuntimeArgs) -> Option<Element> {
-    parse_optional_arg(args, DELEGATOR_ARG_KEY, "delegator", false, identity)
+fn parse_old_validator(args: &RuntimeArgs, config: &ParserConfig) -> Vec<Element> {
+    parse_public_key_arg(args, VALIDATOR_ARG_KEY, "old", config)
 }
 
-fn parse_validator(args: &RuntimeArgs) -> Option<Element> {
-    parse_optional_arg(args, VALIDATOR_ARG_KEY, "validator", false, identity)
-}
-
-fn parse_old_validator(args: &RuntimeArgs) -> Option<Element> {
-    parse_optional_arg(args, VALIDATOR_ARG_KEY, "old", false, identity)
-}
-
-fn parse_new_validator(args: &RuntimeArgs) -> Option<Element> {
-    parse_optional_arg(args, NEW_VALIDATOR_ARG_KEY, "new", false, identity)
+fn parse_new_validator(args: &RuntimeArgs, config: &ParserConfig) -> Vec<Element> {
+    parse_public_key_arg(args, NEW_VALIDATOR_ARG_KEY, "new", config)
 }
 
 fn is_entrypoint(item: &ExecutableDeployItem, expected: &str) -> bool {
@@ -188,3 +184,45 @@ fn is_entrypoint(item: &ExecutableDeployItem, expected: &str) -> bool {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+    use casper_types::{
+        account::AccountHash, runtime_args, PublicKey, RuntimeArgs, SecretKey, U512,
+    };
+
+    use super::{parse_delegation, DELEGATOR_ARG_KEY, VALIDATOR_ARG_KEY};
+    use crate::{checksummed_hex, parser::config::ParserConfig};
+
+    #[test]
+    fn delegation_shows_account_hashes() {
+        let delegator = PublicKey::from(&SecretKey::ed25519_from_bytes([1u8; 32]).unwrap());
+        let validator = PublicKey::from(&SecretKey::ed25519_from_bytes([2u8; 32]).unwrap());
+        let item = ExecutableDeployItem::StoredContractByName {
+            name: "auction".to_string(),
+            entry_point: "delegate".to_string(),
+            args: runtime_args! {
+                DELEGATOR_ARG_KEY => delegator.clone(),
+                VALIDATOR_ARG_KEY => validator.clone(),
+                "amount" => U512::from(500_000_000_000u64),
+            },
+        };
+        let elements = parse_delegation(&item, &ParserConfig::default().with_account_hashes());
+        let account_hashes: Vec<&str> = elements
+            .iter()
+            .filter(|element| element.name() == "Acct hash")
+            .map(|element| element.value())
+            .collect();
+        assert_eq!(
+            account_hashes,
+            vec![
+                checksummed_hex::encode(AccountHash::from(&delegator)),
+                checksummed_hex::encode(AccountHash::from(&validator)),
+            ]
+        );
+
+        let elements = parse_delegation(&item, &ParserConfig::default());
+        assert!(elements.iter().all(|element| element.name() != "Acct hash"));
+    }
+}
@@ -0,0 +1,124 @@
+/// Maximum number of approval signers listed one by one in expert mode.
+/// Deploys with more approvals than that display only the `Approvals #` count.
+pub(crate) const DEFAULT_APPROVAL_SIGNERS_LIMIT: usize = 5;
+
+/// Maximum number of dependencies listed one by one in expert mode.
+/// The remaining ones are summarized on a single page.
+pub(crate) const DEFAULT_DEPENDENCIES_LIMIT: usize = 5;
+
+/// Unit in which token amounts are displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AmountUnit {
+    /// Raw motes, e.g. `1 000 000 000 motes`.
+    Motes,
+    /// Whole tokens with up to 9 decimal places, e.g. `1.5 CSPR`.
+    Cspr,
+}
+
+/// How digits of the integer part of an amount are grouped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ThousandsSeparator {
+    /// `1 000 000`
+    Space,
+    /// `1,000,000`
+    Comma,
+    /// `1000000`
+    None,
+}
+
+/// Display format of token amounts (payment fee, transferred and delegated amounts).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct AmountFormat {
+    unit: AmountUnit,
+    separator: ThousandsSeparator,
+}
+
+impl AmountFormat {
+    pub(crate) fn new(unit: AmountUnit, separator: ThousandsSeparator) -> Self {
+        AmountFormat { unit, separator }
+    }
+
+    pub(crate) fn unit(&self) -> AmountUnit {
+        self.unit
+    }
+
+    pub(crate) fn separator(&self) -> ThousandsSeparator {
+        self.separator
+    }
+}
+
+impl Default for AmountFormat {
+    fn default() -> Self {
+        AmountFormat::new(AmountUnit::Motes, ThousandsSeparator::Space)
+    }
+}
+
+/// Optional parts of the Ledger representation.
+///
+/// Every option defaults to the original representation, so vectors rendered
+/// with `ParserConfig::default()` do not change.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ParserConfig {
+    /// When set, lists each approval's signer (up to the limit) in expert mode.
+    approval_signers_limit: Option<usize>,
+    /// When set, lists the deploy's dependencies (up to the limit) in expert mode.
+    dependencies_limit: Option<usize>,
+    /// Unit and digit grouping of the displayed amounts.
+    amount_format: AmountFormat,
+    /// Whether to display access rights of purses used in native transfers.
+    uref_access_rights: bool,
+    /// Whether to display account hashes derived from public keys of recipients, delegators and validators.
+    account_hashes: bool,
+}
+
+impl ParserConfig {
+    /// Lists the signers of the deploy's approvals, as long as there are at most `limit` of them.
+    pub(crate) fn with_approval_signers(mut self, limit: usize) -> Self {
+        self.approval_signers_limit = Some(limit);
+        self
+    }
+
+    /// Lists at most `limit` of the deploy's dependencies, followed by a summary of the ones left out.
+    pub(crate) fn with_dependencies(mut self, limit: usize) -> Self {
+        self.dependencies_limit = Some(limit);
+        self
+    }
+
+    /// Displays amounts using `amount_format` instead of space-separated motes.
+    pub(crate) fn with_amount_format(mut self, amount_format: AmountFormat) -> Self {
+        self.amount_format = amount_format;
+        self
+    }
+
+    /// Displays access rights of the `source` and `target` purses of native transfers in expert mode.
+    pub(crate) fn with_uref_access_rights(mut self) -> Self {
+        self.uref_access_rights = true;
+        self
+    }
+
+    /// Displays the account hash next to every public key of a transfer recipient, delegator or validator.
+    pub(crate) fn with_account_hashes(mut self) -> Self {
+        self.account_hashes = true;
+        self
+    }
+
+    pub(crate) fn approval_signers_limit(&self) -> Option<usize> {
+        self.approval_signers_limit
+    }
+
+    pub(crate) fn dependencies_limit(&self) -> Option<usize> {
+        self.dependencies_limit
+    }
+
+    pub(crate) fn amount_format(&self) -> &AmountFormat {
+        &self.amount_format
+    }
+
+    pub(crate) fn uref_access_rights(&self) -> bool {
+        self.uref_access_rights
+    }
+
+    pub(crate) fn account_hashes(&self) -> bool {
+        self.account_hashes
+    }
+}
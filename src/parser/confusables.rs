@@ -0,0 +1,231 @@
+//! Analysis of the text the user reads to recognize what they approve - contract names and string args -
+//! for characters that make it look like something else: letters of mixed scripts
+//! (e.g. the Cyrillic `а` in `аuction`), words spelled entirely with Latin lookalikes of another script
+//! and invisible characters.
+
+use std::fmt::Display;
+
+use casper_types::{CLType, CLValue};
+use itertools::Itertools;
+
+use crate::ledger::Element;
+
+/// Script of a letter. Characters that aren't letters (digits, punctuation, whitespace, marks) have none.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    /// Any other script.
+    Other,
+}
+
+impl Script {
+    fn of(c: char) -> Option<Script> {
+        if !c.is_alphabetic() {
+            return None;
+        }
+        let script = match c {
+            'a'..='z' | 'A'..='Z' | '\u{00C0}'..='\u{024F}' | '\u{1E00}'..='\u{1EFF}' => {
+                Script::Latin
+            }
+            '\u{0370}'..='\u{03FF}' | '\u{1F00}'..='\u{1FFF}' => Script::Greek,
+            '\u{0400}'..='\u{052F}' => Script::Cyrillic,
+            _ => Script::Other,
+        };
+        Some(script)
+    }
+}
+
+impl Display for Script {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Script::Latin => write!(f, "Latin"),
+            Script::Greek => write!(f, "Greek"),
+            Script::Cyrillic => write!(f, "Cyrillic"),
+            Script::Other => write!(f, "other"),
+        }
+    }
+}
+
+/// Greek and Cyrillic letters indistinguishable from Latin ones, with the Latin letter they look like.
+const LATIN_LOOKALIKES: [(char, char); 38] = [
+    // Cyrillic
+    ('а', 'a'),
+    ('е', 'e'),
+    ('о', 'o'),
+    ('р', 'p'),
+    ('с', 'c'),
+    ('у', 'y'),
+    ('х', 'x'),
+    ('і', 'i'),
+    ('ј', 'j'),
+    ('ѕ', 's'),
+    ('ԁ', 'd'),
+    ('һ', 'h'),
+    ('А', 'A'),
+    ('В', 'B'),
+    ('Е', 'E'),
+    ('К', 'K'),
+    ('М', 'M'),
+    ('Н', 'H'),
+    ('О', 'O'),
+    ('Р', 'P'),
+    ('С', 'C'),
+    ('Т', 'T'),
+    ('Х', 'X'),
+    ('І', 'I'),
+    ('Ј', 'J'),
+    ('Ѕ', 'S'),
+    // Greek
+    ('ο', 'o'),
+    ('ν', 'v'),
+    ('Α', 'A'),
+    ('Β', 'B'),
+    ('Ε', 'E'),
+    ('Ζ', 'Z'),
+    ('Η', 'H'),
+    ('Ι', 'I'),
+    ('Κ', 'K'),
+    ('Μ', 'M'),
+    ('Ν', 'N'),
+    ('Ο', 'O'),
+];
+
+fn latin_lookalike(c: char) -> Option<char> {
+    LATIN_LOOKALIKES
+        .iter()
+        .find(|(lookalike, _)| *lookalike == c)
+        .map(|(_, latin)| *latin)
+}
+
+/// Characters that aren't displayed at all, but change how the text around them is displayed or compared:
+/// the soft hyphen, zero-width characters, bidirectional controls and the byte order mark.
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{2069}'
+            | '\u{FEFF}'
+    )
+}
+
+/// Reason why a text may look like something else than it is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Confusion {
+    /// Invisible character, e.g. the zero-width space or the right-to-left override.
+    Invisible { found: char },
+    /// Letters of more than one script, e.g. Latin `uction` with the Cyrillic `а`.
+    MixedScripts { scripts: Vec<Script> },
+    /// Letters of a single script other than Latin, all of them lookalikes of the Latin ones,
+    /// e.g. the Cyrillic `рау` looking like `pay`.
+    LatinLookalike { script: Script, looks_like: String },
+}
+
+impl Display for Confusion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Confusion::Invisible { found } => {
+                write!(f, "has invisible U+{:04X}", u32::from(*found))
+            }
+            Confusion::MixedScripts { scripts } => {
+                write!(f, "mixes {}", scripts.iter().join(", "))
+            }
+            Confusion::LatinLookalike { script, looks_like } => {
+                write!(f, "is {}, looks like {}", script, looks_like)
+            }
+        }
+    }
+}
+
+/// Checks the text for characters that make it look like something else than it is.
+/// Reports the first confusion found: invisible characters, then mixed scripts, then Latin lookalikes.
+pub(crate) fn check_confusables(text: &str) -> Result<(), Confusion> {
+    if let Some(found) = text.chars().find(|c| is_invisible(*c)) {
+        return Err(Confusion::Invisible { found });
+    }
+    let scripts: Vec<Script> = text
+        .chars()
+        .filter_map(Script::of)
+        .unique()
+        .sorted()
+        .collect();
+    match scripts.as_slice() {
+        [] | [Script::Latin] => Ok(()),
+        [script] => {
+            let looks_like: Option<String> = text
+                .chars()
+                .map(|c| match Script::of(c) {
+                    Some(_) => latin_lookalike(c),
+                    None => Some(c),
+                })
+                .collect();
+            match looks_like {
+                Some(looks_like) => Err(Confusion::LatinLookalike {
+                    script: *script,
+                    looks_like,
+                }),
+                None => Ok(()),
+            }
+        }
+        mixed => Err(Confusion::MixedScripts {
+            scripts: mixed.to_vec(),
+        }),
+    }
+}
+
+/// Returns a regular-mode warning about the `text` displayed as `subject`, if it may look like something else,
+/// e.g. `name mixes Latin, Cyrillic`.
+pub(crate) fn confusables_warning(subject: &str, text: &str) -> Option<Element> {
+    let confusion = check_confusables(text).err()?;
+    Some(Element::regular(
+        "warning",
+        format!("{} {}", subject, confusion),
+    ))
+}
+
+/// Same as `confusables_warning`, for the argument value displayed as `subject`. Only `String` values are checked.
+pub(crate) fn string_arg_warning(subject: &str, cl_value: &CLValue) -> Option<Element> {
+    if cl_value.cl_type() != &CLType::String {
+        return None;
+    }
+    let text: String = cl_value.clone().into_t().ok()?;
+    confusables_warning(subject, &text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_confusables, Confusion, Script};
+
+    #[test]
+    fn latin_and_foreign_words_pass() {
+        assert_eq!(check_confusables("auction"), Ok(()));
+        assert_eq!(check_confusables("Token transfer 2"), Ok(()));
+        assert_eq!(check_confusables("café"), Ok(()));
+        // Not all of the letters look Latin.
+        assert_eq!(check_confusables("жук"), Ok(()));
+        assert_eq!(check_confusables(""), Ok(()));
+    }
+
+    #[test]
+    fn confusions_are_detected() {
+        assert_eq!(
+            check_confusables("\u{0430}uction"),
+            Err(Confusion::MixedScripts {
+                scripts: vec![Script::Latin, Script::Cyrillic]
+            })
+        );
+        assert_eq!(
+            check_confusables("\u{0440}\u{0430}\u{0443}"),
+            Err(Confusion::LatinLookalike {
+                script: Script::Cyrillic,
+                looks_like: "pay".to_string()
+            })
+        );
+        let invisible = check_confusables("Token\u{200B}transfer").unwrap_err();
+        assert_eq!(invisible.to_string(), "has invisible U+200B");
+    }
+}
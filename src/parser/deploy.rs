@@ -1,9 +1,13 @@
 use std::collections::BTreeMap;
 
 use crate::{
+    checksummed_hex,
     ledger::{Element, TxnPhase},
-    parser::{runtime_args::parse_optional_arg, utils::timestamp_to_seconds_res},
-    utils::parse_public_key,
+    parser::{
+        config::{AmountFormat, AmountUnit, ParserConfig, ThousandsSeparator},
+        utils::timestamp_to_seconds_res,
+    },
+    utils::{cl_value_to_string, parse_public_key},
 };
 use casper_execution_engine::core::engine_state::ExecutableDeployItem;
 use casper_hashing::Digest;
@@ -20,10 +24,12 @@ use super::{
         is_delegate, is_redelegate, is_undelegate, parse_delegation, parse_redelegation,
         parse_undelegation,
     },
+    confusables::{confusables_warning, string_arg_warning},
     runtime_args::{parse_runtime_args, parse_transfer_args},
+    validation::validate_placement,
 };
 
-pub(crate) fn parse_deploy_header(dh: &DeployHeader) -> Vec<Element> {
+pub(crate) fn parse_deploy_header(dh: &DeployHeader, config: &ParserConfig) -> Vec<Element> {
     let mut elements = vec![];
     elements.push(Element::regular("chain ID", dh.chain_name().to_string()));
     elements.push(Element::regular("account", parse_public_key(dh.account())));
@@ -37,16 +43,85 @@ pub(crate) fn parse_deploy_header(dh: &DeployHeader) -> Vec<Element> {
         "Deps #",
         format!("{:?}", dh.dependencies().len()),
     ));
+    if let Some(limit) = config.dependencies_limit() {
+        elements.extend(parse_dependencies(dh, limit));
+    }
+    elements
+}
+
+/// Lists up to `limit` dependencies of the deploy.
+/// If there are more, the rest is summarized on a single page.
+fn parse_dependencies(dh: &DeployHeader, limit: usize) -> Vec<Element> {
+    let mut elements: Vec<Element> = dh
+        .dependencies()
+        .iter()
+        .take(limit)
+        .enumerate()
+        .map(|(idx, dependency)| {
+            Element::expert(
+                &format!("Dep {}", idx + 1),
+                checksummed_hex::encode(dependency.inner()),
+            )
+        })
+        .collect();
+    let not_shown = dh.dependencies().len().saturating_sub(limit);
+    if not_shown > 0 {
+        elements.push(Element::expert(
+            "Deps more",
+            format!("{} not shown", not_shown),
+        ));
+    }
     elements
 }
 
-pub(crate) fn parse_phase(item: &ExecutableDeployItem, phase: TxnPhase) -> Vec<Element> {
+pub(crate) fn parse_phase(
+    item: &ExecutableDeployItem,
+    phase: TxnPhase,
+    config: &ParserConfig,
+) -> Vec<Element> {
+    // Items that can't be executed in their phase are preceded by a warning with the reason.
+    let warning = match validate_placement(phase, item) {
+        Err(error) => Some(Element::regular("warning", error.to_string())),
+        Ok(()) => custom_payment_warning(phase, item),
+    };
+    let mut elements: Vec<Element> = warning.into_iter().collect();
+    // Contract name is checked here, so that the warning stays in regular mode even when
+    // the name itself is displayed in expert mode only, like for auction calls.
+    elements.extend(contract_name(item).and_then(|name| confusables_warning("name", name)));
+    elements.extend(parse_item(item, phase, config));
+    elements
+}
+
+/// Name of the contract called by name.
+fn contract_name(item: &ExecutableDeployItem) -> Option<&str> {
+    match item {
+        ExecutableDeployItem::StoredContractByName { name, .. }
+        | ExecutableDeployItem::StoredVersionedContractByName { name, .. } => Some(name.as_str()),
+        _ => None,
+    }
+}
+
+/// Payment other than the system one runs custom logic that decides what's paid (and what else happens),
+/// so it's preceded by a warning - its details follow, just like the session's.
+fn custom_payment_warning(phase: TxnPhase, item: &ExecutableDeployItem) -> Option<Element> {
+    match item {
+        ExecutableDeployItem::ModuleBytes { module_bytes, .. }
+            if is_system_payment(phase, module_bytes) =>
+        {
+            None
+        }
+        _ if phase.is_payment() => Some(Element::regular("warning", "custom payment".to_string())),
+        _ => None,
+    }
+}
+
+fn parse_item(item: &ExecutableDeployItem, phase: TxnPhase, config: &ParserConfig) -> Vec<Element> {
     if is_delegate(item) {
-        parse_delegation(item)
+        parse_delegation(item, config)
     } else if is_undelegate(item) {
-        parse_undelegation(item)
+        parse_undelegation(item, config)
     } else if is_redelegate(item) {
-        parse_redelegation(item)
+        parse_redelegation(item, config)
     } else {
         let mut elements: Vec<Element> = deploy_type(phase, item);
         match item {
@@ -54,7 +129,7 @@ pub(crate) fn parse_phase(item: &ExecutableDeployItem, phase: TxnPhase) -> Vec<E
                 if is_system_payment(phase, module_bytes) =>
             {
                 // The only required argument for the system payment is `amount`.
-                elements.extend(parse_fee(args));
+                elements.extend(parse_fee(args, config));
                 let args_sans_amount = remove_amount_arg(args.clone());
                 if !args_sans_amount.is_empty() {
                     // If system payment had more args than the required `amount` then they should be parsed.
@@ -65,42 +140,41 @@ pub(crate) fn parse_phase(item: &ExecutableDeployItem, phase: TxnPhase) -> Vec<E
                 module_bytes: _,
                 args,
             } => {
-                elements.extend(parse_amount(args));
+                elements.extend(parse_amount(args, config));
                 elements.extend(parse_runtime_args(&phase, args));
             }
             ExecutableDeployItem::StoredContractByHash {
                 entry_point, args, ..
             } => {
                 elements.push(entrypoint(entry_point));
-                elements.extend(parse_amount(args));
+                elements.extend(parse_amount(args, config));
                 elements.extend(parse_runtime_args(&phase, args));
             }
             ExecutableDeployItem::StoredContractByName {
                 entry_point, args, ..
             } => {
                 elements.push(entrypoint(entry_point));
-                elements.extend(parse_amount(args));
+                elements.extend(parse_amount(args, config));
                 elements.extend(parse_runtime_args(&phase, args));
             }
             ExecutableDeployItem::StoredVersionedContractByHash {
                 entry_point, args, ..
             } => {
                 elements.push(entrypoint(entry_point));
-                elements.extend(parse_amount(args));
+                elements.extend(parse_amount(args, config));
                 elements.extend(parse_runtime_args(&phase, args));
             }
             ExecutableDeployItem::StoredVersionedContractByName {
                 entry_point, args, ..
             } => {
                 elements.push(entrypoint(entry_point));
-                elements.extend(parse_amount(args));
+                elements.extend(parse_amount(args, config));
                 elements.extend(parse_runtime_args(&phase, args));
             }
             ExecutableDeployItem::Transfer { args } => {
-                elements.extend(parse_transfer_args(args));
+                elements.extend(parse_transfer_args(args, config));
                 let args_sans_transfer = remove_transfer_args(args.clone());
                 if !args_sans_transfer.is_empty() {
-                    println!("{:?}", args_sans_transfer);
                     // If there are more arguments left that were not used, display digest of args.
                     elements.extend(parse_runtime_args(&phase, args));
                 }
@@ -180,12 +254,13 @@ pub(crate) fn deploy_type(phase: TxnPhase, item: &ExecutableDeployItem) -> Vec<E
     }
 }
 
+// A specific version is displayed in expert mode only. The latest version is displayed in regular mode -
+// the code that runs is whatever the latest version is at the time of execution, not the one the user reviewed.
 fn parse_version(version: &Option<u32>) -> Element {
-    let version = match version {
-        None => "latest".to_string(),
-        Some(version) => format!("{}", version),
-    };
-    Element::expert("version", version)
+    match version {
+        None => Element::regular("version", "latest".to_string()),
+        Some(version) => Element::expert("version", format!("{}", version)),
+    }
 }
 
 // Payment is a system type of payment when the `module_bytes` are empty.
@@ -210,46 +285,167 @@ fn remove_transfer_args(args: RuntimeArgs) -> RuntimeArgs {
     tree.into()
 }
 
-fn format_amount(motes: U512) -> String {
-    format!("{} motes", motes.separate_with_spaces())
+// Number of motes in one CSPR.
+const MOTES_PER_CSPR: u64 = 1_000_000_000;
+// Number of decimal places of CSPR amounts.
+const CSPR_DECIMALS: usize = 9;
+
+fn format_amount(motes: U512, format: &AmountFormat) -> String {
+    match format.unit() {
+        AmountUnit::Motes => format!("{} motes", separate_thousands(motes, format.separator())),
+        AmountUnit::Cspr => {
+            let (whole, fraction) = motes.div_mod(U512::from(MOTES_PER_CSPR));
+            let whole = separate_thousands(whole, format.separator());
+            if fraction.is_zero() {
+                return format!("{} CSPR", whole);
+            }
+            // Fraction is always lower than `MOTES_PER_CSPR` so it fits in `u64`.
+            let fraction = format!("{:0width$}", fraction.as_u64(), width = CSPR_DECIMALS);
+            format!("{}.{} CSPR", whole, fraction.trim_end_matches('0'))
+        }
+    }
 }
 
-pub(crate) fn parse_fee(args: &RuntimeArgs) -> Option<Element> {
-    parse_motes(args, "fee")
+fn separate_thousands(value: U512, separator: ThousandsSeparator) -> String {
+    match separator {
+        ThousandsSeparator::Space => value.separate_with_spaces(),
+        ThousandsSeparator::Comma => value.separate_with_commas(),
+        ThousandsSeparator::None => value.to_string(),
+    }
 }
 
-pub(crate) fn parse_amount(args: &RuntimeArgs) -> Option<Element> {
-    parse_motes(args, "amount")
+pub(crate) fn parse_fee(args: &RuntimeArgs, config: &ParserConfig) -> Vec<Element> {
+    parse_motes(args, "fee", config)
 }
 
-fn parse_motes(args: &RuntimeArgs, ledger_label: &str) -> Option<Element> {
-    let f = |amount_str: String| {
-        let motes_amount = U512::from_dec_str(&amount_str).unwrap();
-        format_amount(motes_amount)
+pub(crate) fn parse_amount(args: &RuntimeArgs, config: &ParserConfig) -> Vec<Element> {
+    parse_motes(args, "amount", config)
+}
+
+/// Parses the amount, preceded by a warning if it's a `String` that may look like something else.
+fn parse_motes(args: &RuntimeArgs, ledger_label: &str, config: &ParserConfig) -> Vec<Element> {
+    let cl_value = match args.get(mint::ARG_AMOUNT) {
+        Some(cl_value) => cl_value,
+        None => return vec![],
+    };
+    // Only `U512` amounts are motes, values of other types are displayed as they are.
+    let value = match cl_value.clone().into_t::<U512>() {
+        Ok(motes_amount) => format_amount(motes_amount, config.amount_format()),
+        Err(_) => cl_value_to_string(cl_value),
     };
-    parse_optional_arg(args, mint::ARG_AMOUNT, ledger_label, false, f)
+    let mut elements: Vec<Element> = string_arg_warning(ledger_label, cl_value)
+        .into_iter()
+        .collect();
+    elements.push(Element::regular(ledger_label, value));
+    elements
 }
 
 #[cfg(test)]
 mod amount {
     use casper_types::U512;
 
-    use crate::parser::deploy::format_amount;
+    use crate::parser::{
+        config::{AmountFormat, AmountUnit, ThousandsSeparator},
+        deploy::format_amount,
+    };
 
     #[test]
     fn amount_space_separated() {
+        let format = AmountFormat::default();
         let one: U512 = 1u8.into();
         let expected = "1 motes".to_string();
-        assert_eq!(expected, format_amount(one));
+        assert_eq!(expected, format_amount(one, &format));
         let thousand: U512 = 1_000u32.into();
         let expected = "1 000 motes".to_string();
-        assert_eq!(expected, format_amount(thousand));
+        assert_eq!(expected, format_amount(thousand, &format));
         let ten_thousand: U512 = 10_000u64.into();
         let expected = "10 000 motes".to_string();
-        assert_eq!(expected, format_amount(ten_thousand));
+        assert_eq!(expected, format_amount(ten_thousand, &format));
         let ten_billion: U512 = U512::from(10000000000u64);
         let expected = "10 000 000 000 motes".to_string();
-        assert_eq!(expected, format_amount(ten_billion));
+        assert_eq!(expected, format_amount(ten_billion, &format));
+    }
+
+    #[test]
+    fn amount_comma_separated() {
+        let format = AmountFormat::new(AmountUnit::Motes, ThousandsSeparator::Comma);
+        let ten_billion: U512 = U512::from(10000000000u64);
+        let expected = "10,000,000,000 motes".to_string();
+        assert_eq!(expected, format_amount(ten_billion, &format));
+    }
+
+    #[test]
+    fn amount_in_cspr() {
+        let format = AmountFormat::new(AmountUnit::Cspr, ThousandsSeparator::Space);
+        let zero = U512::zero();
+        assert_eq!("0 CSPR", format_amount(zero, &format));
+        let one_mote: U512 = 1u8.into();
+        assert_eq!("0.000000001 CSPR", format_amount(one_mote, &format));
+        let one_cspr = U512::from(1_000_000_000u64);
+        assert_eq!("1 CSPR", format_amount(one_cspr, &format));
+        let one_and_a_half = U512::from(1_500_000_000u64);
+        assert_eq!("1.5 CSPR", format_amount(one_and_a_half, &format));
+        let many = U512::from(1_234_567_000_000_001u64);
+        assert_eq!("1 234 567.000000001 CSPR", format_amount(many, &format));
+    }
+
+    #[test]
+    fn amount_in_cspr_comma_separated() {
+        let format = AmountFormat::new(AmountUnit::Cspr, ThousandsSeparator::Comma);
+        let many = U512::from(1_234_567_100_000_000u64);
+        assert_eq!("1,234,567.1 CSPR", format_amount(many, &format));
+    }
+
+    #[test]
+    fn max_amount_in_cspr() {
+        let format = AmountFormat::new(AmountUnit::Cspr, ThousandsSeparator::None);
+        let expected = "13407807929942597099574024998205846127479365820592393377723561443721764030073546976801874298166903427690031858186486050853753882811946569946433649.006084095 CSPR";
+        assert_eq!(expected, format_amount(U512::MAX, &format));
+    }
+}
+
+#[cfg(test)]
+mod payment {
+    use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+    use casper_types::{bytesrepr::Bytes, runtime_args, RuntimeArgs, U512};
+
+    use crate::{
+        ledger::TxnPhase,
+        parser::{config::ParserConfig, deploy::parse_phase},
+    };
+
+    fn names(item: &ExecutableDeployItem, phase: TxnPhase) -> Vec<String> {
+        parse_phase(item, phase, &ParserConfig::default())
+            .iter()
+            .map(|element| element.name().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn custom_payment_is_preceded_by_warning() {
+        let args = runtime_args! { "amount" => U512::from(1_000u64) };
+        let system = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: args.clone(),
+        };
+        assert_eq!(names(&system, TxnPhase::Payment), vec!["Fee"]);
+
+        let custom = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::from(vec![0u8; 8]),
+            args,
+        };
+        assert_eq!(
+            names(&custom, TxnPhase::Payment),
+            vec!["Warning", "Payment", "Cntrct hash", "Amount", "Args hash"]
+        );
+
+        let stored = ExecutableDeployItem::StoredContractByName {
+            name: "payment_contract".to_string(),
+            entry_point: "pay".to_string(),
+            args: RuntimeArgs::new(),
+        };
+        assert_eq!(names(&stored, TxnPhase::Payment)[0], "Warning");
+        assert_ne!(names(&stored, TxnPhase::Session)[0], "Warning");
     }
 }
 
@@ -257,12 +453,26 @@ pub(crate) fn identity<T>(el: T) -> T {
     el
 }
 
-pub(crate) fn parse_approvals(d: &Deploy) -> Vec<Element> {
+pub(crate) fn parse_approvals(d: &Deploy, config: &ParserConfig) -> Vec<Element> {
     let approvals_count = d.approvals().len();
-    vec![Element::expert(
+    let mut elements = vec![Element::expert(
         "Approvals #",
         format!("{}", approvals_count),
-    )]
+    )];
+    // Listing too many signers would make the expert view unreadable,
+    // in that case the approvals count above is all we display.
+    let list_signers = config
+        .approval_signers_limit()
+        .map_or(false, |limit| approvals_count <= limit);
+    if list_signers {
+        for (idx, approval) in d.approvals().iter().enumerate() {
+            elements.push(Element::expert(
+                &format!("Signer {}", idx + 1),
+                parse_public_key(approval.signer()),
+            ));
+        }
+    }
+    elements
 }
 
 fn entrypoint(entry_point: &str) -> Element {
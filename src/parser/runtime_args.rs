@@ -1,10 +1,17 @@
+use crate::checksummed_hex;
 use crate::ledger::{Element, TxnPhase};
-use crate::utils::cl_value_to_string;
+use crate::parser::config::ParserConfig;
+use crate::utils::{
+    access_rights_to_string, cl_value_to_string, public_key_account_hash, uref_access_rights,
+};
 use casper_types::bytesrepr::ToBytes;
 use casper_types::system::mint::{ARG_ID, ARG_SOURCE, ARG_TARGET, ARG_TO};
 use casper_types::RuntimeArgs;
 
-use super::deploy::{identity, parse_amount};
+use super::{
+    confusables::string_arg_warning,
+    deploy::{identity, parse_amount},
+};
 
 /// Parses all contract arguments into a form:
 /// arg-n-name: <name>
@@ -46,7 +53,7 @@ pub(crate) fn parse_optional_arg<F: Fn(String) -> String>(
     label: &str,
     expert: bool,
     f: F,
-) -> Option<Element> {
+) -> Vec<Element> {
     match args.get(key) {
         Some(cl_value) => {
             let value = f(cl_value_to_string(cl_value));
@@ -55,9 +62,13 @@ pub(crate) fn parse_optional_arg<F: Fn(String) -> String>(
             } else {
                 Element::regular(label, value)
             };
-            Some(element)
+            // `String` values that may look like something else are preceded by a warning, in regular mode.
+            let mut elements: Vec<Element> =
+                string_arg_warning(label, cl_value).into_iter().collect();
+            elements.push(element);
+            elements
         }
-        None => None,
+        None => vec![],
     }
 }
 
@@ -67,15 +78,109 @@ pub(crate) fn parse_optional_arg<F: Fn(String) -> String>(
 /// * ID
 /// Optional fields:
 /// * source
-pub(crate) fn parse_transfer_args(args: &RuntimeArgs) -> Vec<Element> {
-    let mut elements: Vec<Element> = parse_optional_arg(args, ARG_TO, "recipient", false, identity)
-        .into_iter()
-        .collect();
+pub(crate) fn parse_transfer_args(args: &RuntimeArgs, config: &ParserConfig) -> Vec<Element> {
+    let mut elements: Vec<Element> = parse_optional_arg(args, ARG_TO, "recipient", false, identity);
     elements.extend(parse_optional_arg(args, ARG_SOURCE, "from", true, identity));
+    if config.uref_access_rights() {
+        elements.extend(parse_access_rights(args, ARG_SOURCE, "From rights"));
+    }
     elements.extend(parse_optional_arg(
         args, ARG_TARGET, "target", false, identity,
     ));
-    elements.extend(parse_amount(args));
+    if config.uref_access_rights() {
+        elements.extend(parse_access_rights(args, ARG_TARGET, "Tgt rights"));
+    }
+    if config.account_hashes() {
+        elements.extend(parse_account_hash(args, ARG_TARGET));
+    }
+    elements.extend(parse_amount(args, config));
     elements.extend(parse_optional_arg(args, ARG_ID, "ID", true, identity));
     elements
 }
+
+/// Displays access rights of the purse under `key`, as long as it's a `URef`.
+fn parse_access_rights(args: &RuntimeArgs, key: &str, label: &str) -> Option<Element> {
+    let access_rights = args.get(key).and_then(uref_access_rights)?;
+    Some(Element::expert(
+        label,
+        access_rights_to_string(access_rights),
+    ))
+}
+
+/// Displays the account hash derived from the public key under `key`, as long as it's a `PublicKey`.
+/// Uses the same format as `Key::Account` arguments, so it can be cross-checked with block explorers.
+fn parse_account_hash(args: &RuntimeArgs, key: &str) -> Option<Element> {
+    let account_hash = args.get(key).and_then(public_key_account_hash)?;
+    Some(Element::expert(
+        "Acct hash",
+        checksummed_hex::encode(account_hash),
+    ))
+}
+
+/// Parses the required public key argument of the auction contract calls.
+/// When enabled in `config`, it's followed by the account hash derived from that key.
+pub(crate) fn parse_public_key_arg(
+    args: &RuntimeArgs,
+    key: &str,
+    label: &str,
+    config: &ParserConfig,
+) -> Vec<Element> {
+    let mut elements = parse_optional_arg(args, key, label, false, identity);
+    if config.account_hashes() {
+        elements.extend(parse_account_hash(args, key));
+    }
+    elements
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{
+        account::AccountHash,
+        runtime_args,
+        system::mint::{ARG_AMOUNT, ARG_SOURCE, ARG_TARGET},
+        AccessRights, PublicKey, RuntimeArgs, SecretKey, URef, U512,
+    };
+
+    use super::parse_transfer_args;
+    use crate::{checksummed_hex, parser::config::ParserConfig};
+
+    fn names(args: &RuntimeArgs, config: &ParserConfig) -> Vec<String> {
+        parse_transfer_args(args, config)
+            .iter()
+            .map(|element| element.name().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn transfer_access_rights_labels() {
+        let args = runtime_args! {
+            ARG_SOURCE => URef::new([1u8; 32], AccessRights::READ_ADD_WRITE),
+            ARG_TARGET => URef::new([2u8; 32], AccessRights::ADD),
+            ARG_AMOUNT => U512::from(2_500_000_000u64),
+        };
+        let names = names(&args, &ParserConfig::default().with_uref_access_rights());
+        assert!(names.iter().any(|name| name == "From rights"));
+        assert!(names.iter().any(|name| name == "Tgt rights"));
+    }
+
+    #[test]
+    fn transfer_to_public_key_shows_account_hash() {
+        let target = PublicKey::from(&SecretKey::ed25519_from_bytes([5u8; 32]).unwrap());
+        let args = runtime_args! {
+            ARG_TARGET => target.clone(),
+            ARG_AMOUNT => U512::from(2_500_000_000u64),
+        };
+        assert!(!names(&args, &ParserConfig::default()).contains(&"Acct hash".to_string()));
+
+        let elements = parse_transfer_args(&args, &ParserConfig::default().with_account_hashes());
+        let account_hash = elements
+            .iter()
+            .find(|element| element.name() == "Acct hash")
+            .expect("account hash of the target");
+        assert!(account_hash.is_expert());
+        assert_eq!(
+            account_hash.value(),
+            checksummed_hex::encode(AccountHash::from(&target))
+        );
+    }
+}
@@ -0,0 +1,433 @@
+//! Type-strict validation of the well-known arguments of native transfers and auction calls,
+//! of the items placed in a phase they can't be executed in,
+//! and of the header fields that make a deploy impossible to execute on any network.
+//!
+//! Parsers rely on these rules to decide whether a deploy can be displayed as a transfer or an auction action,
+//! test data relies on them to compute validity of the samples.
+
+use std::fmt::Display;
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_node::types::DeployHeader;
+use casper_types::{
+    system::mint::{ARG_AMOUNT, ARG_ID, ARG_SOURCE, ARG_TARGET},
+    CLType, RuntimeArgs, TimeDiff, U512,
+};
+use itertools::Itertools;
+
+use crate::ledger::TxnPhase;
+
+use super::auction::{
+    DELEGATOR_ARG_KEY, NEW_VALIDATOR_ARG_KEY, REDELEGATE_ENTRYPOINT, VALIDATOR_ARG_KEY,
+};
+
+/// Reason why a well-known argument did not pass validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ArgError {
+    /// Required argument is not present.
+    Missing { name: &'static str },
+    /// Argument is present but its type is not one of the expected ones.
+    InvalidType {
+        name: &'static str,
+        expected: Vec<CLType>,
+        found: CLType,
+    },
+}
+
+impl Display for ArgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgError::Missing { name } => write!(f, "missing {}", name),
+            ArgError::InvalidType {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{} must be {}, found {:?}",
+                name,
+                expected
+                    .iter()
+                    .map(|cl_type| format!("{:?}", cl_type))
+                    .join(" or "),
+                found
+            ),
+        }
+    }
+}
+
+/// Reason why an item can't be executed in the phase it's placed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PlacementError {
+    /// Native transfer can only be a session - there's no payment logic in it.
+    TransferAsPayment,
+    /// Empty module bytes are the system payment, there's no session code to execute.
+    EmptySessionModuleBytes,
+}
+
+impl Display for PlacementError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlacementError::TransferAsPayment => write!(f, "transfer as payment"),
+            PlacementError::EmptySessionModuleBytes => write!(f, "empty session code"),
+        }
+    }
+}
+
+/// Reason why a deploy header makes the deploy impossible to execute.
+///
+/// These rules don't depend on the chainspec - its limits are checked by `ChainspecLimits::check`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum HeaderError {
+    /// Deploy expires at the very moment it's created.
+    ZeroTtl,
+    /// Payment can't be converted into gas - gas limit is the payment amount divided by the gas price.
+    ZeroGasPrice,
+    /// Payment amount buys no gas at all.
+    GasPriceAbovePayment { gas_price: u64, payment: U512 },
+}
+
+impl Display for HeaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeaderError::ZeroTtl => write!(f, "ttl must be greater than 0"),
+            HeaderError::ZeroGasPrice => write!(f, "gas price must be greater than 0"),
+            HeaderError::GasPriceAbovePayment { gas_price, payment } => write!(
+                f,
+                "gas price {} exceeds the payment of {} motes",
+                gas_price, payment
+            ),
+        }
+    }
+}
+
+/// Checks that the argument is present and has one of the `expected` types.
+fn required(args: &RuntimeArgs, name: &'static str, expected: &[CLType]) -> Result<(), ArgError> {
+    match args.get(name) {
+        Some(_) => optional(args, name, expected),
+        None => Err(ArgError::Missing { name }),
+    }
+}
+
+/// Checks that the argument, if present, has one of the `expected` types.
+fn optional(args: &RuntimeArgs, name: &'static str, expected: &[CLType]) -> Result<(), ArgError> {
+    match args.get(name) {
+        Some(cl_value) if !expected.contains(cl_value.cl_type()) => Err(ArgError::InvalidType {
+            name,
+            expected: expected.to_vec(),
+            found: cl_value.cl_type().clone(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Native transfer requires:
+/// * `amount` of type `U512`
+/// * `id` of type `Option<u64>`
+/// * `target` of type `URef`, `Key`, `PublicKey` or raw 32 bytes of an account hash
+///
+/// Optional `source` has to be a `URef`.
+pub(crate) fn validate_transfer_args(args: &RuntimeArgs) -> Result<(), ArgError> {
+    required(args, ARG_AMOUNT, &[CLType::U512])?;
+    required(args, ARG_ID, &[CLType::Option(Box::new(CLType::U64))])?;
+    required(
+        args,
+        ARG_TARGET,
+        &[
+            CLType::URef,
+            CLType::Key,
+            CLType::PublicKey,
+            CLType::ByteArray(32),
+        ],
+    )?;
+    optional(args, ARG_SOURCE, &[CLType::URef])
+}
+
+/// Both delegation and undelegation require `delegator` and `validator` public keys and `U512` amount.
+pub(crate) fn validate_delegation_args(args: &RuntimeArgs) -> Result<(), ArgError> {
+    required(args, DELEGATOR_ARG_KEY, &[CLType::PublicKey])?;
+    required(args, VALIDATOR_ARG_KEY, &[CLType::PublicKey])?;
+    required(args, ARG_AMOUNT, &[CLType::U512])
+}
+
+/// Redelegation requires the same arguments as delegation and the `new_validator` public key.
+pub(crate) fn validate_redelegation_args(args: &RuntimeArgs) -> Result<(), ArgError> {
+    validate_delegation_args(args)?;
+    required(args, NEW_VALIDATOR_ARG_KEY, &[CLType::PublicKey])
+}
+
+/// System payment requires `amount` of type `U512`.
+pub(crate) fn validate_system_payment_args(args: &RuntimeArgs) -> Result<(), ArgError> {
+    required(args, ARG_AMOUNT, &[CLType::U512])
+}
+
+/// Validates the session item.
+///
+/// Native transfers have to follow the rules. Calls to the auction contract with arguments
+/// that don't follow them are not recognized as auction actions and are displayed
+/// as generic contract calls instead - we can't tell what arguments other contracts expect.
+/// The only exception is a call to the `redelegate` entry point without the `new_validator`:
+/// there's nowhere to redelegate to.
+pub(crate) fn validate_session(item: &ExecutableDeployItem) -> Result<(), ArgError> {
+    match item {
+        ExecutableDeployItem::Transfer { args } => validate_transfer_args(args),
+        ExecutableDeployItem::StoredContractByHash {
+            entry_point, args, ..
+        }
+        | ExecutableDeployItem::StoredContractByName {
+            entry_point, args, ..
+        }
+        | ExecutableDeployItem::StoredVersionedContractByHash {
+            entry_point, args, ..
+        }
+        | ExecutableDeployItem::StoredVersionedContractByName {
+            entry_point, args, ..
+        } if entry_point == REDELEGATE_ENTRYPOINT && args.get(NEW_VALIDATOR_ARG_KEY).is_none() => {
+            Err(ArgError::Missing {
+                name: NEW_VALIDATOR_ARG_KEY,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Validates the payment item.
+///
+/// Only the system payment (empty module bytes) has well-known arguments.
+pub(crate) fn validate_payment(item: &ExecutableDeployItem) -> Result<(), ArgError> {
+    match item {
+        ExecutableDeployItem::ModuleBytes { module_bytes, args }
+            if module_bytes.inner_bytes().is_empty() =>
+        {
+            validate_system_payment_args(args)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Validates the payment amount of a deploy paying for the `session`.
+///
+/// A native transfer costs a fixed amount, any other session buys its gas with the `U512` `amount`
+/// passed to the payment - whether it's the system payment or a custom one.
+pub(crate) fn validate_payment_amount(
+    payment: &ExecutableDeployItem,
+    session: &ExecutableDeployItem,
+) -> Result<(), ArgError> {
+    match session {
+        ExecutableDeployItem::Transfer { .. } => Ok(()),
+        _ => required(payment.args(), ARG_AMOUNT, &[CLType::U512]),
+    }
+}
+
+/// Checks that the item can be executed in the `phase` it's placed in.
+pub(crate) fn validate_placement(
+    phase: TxnPhase,
+    item: &ExecutableDeployItem,
+) -> Result<(), PlacementError> {
+    match item {
+        ExecutableDeployItem::Transfer { .. } if phase.is_payment() => {
+            Err(PlacementError::TransferAsPayment)
+        }
+        ExecutableDeployItem::ModuleBytes { module_bytes, .. }
+            if !phase.is_payment() && module_bytes.inner_bytes().is_empty() =>
+        {
+            Err(PlacementError::EmptySessionModuleBytes)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Validates the header of a deploy paid with `payment`.
+///
+/// Gas price is checked against the amount of the system payment only -
+/// we can't tell how much a custom payment contract pays.
+pub(crate) fn validate_header(
+    header: &DeployHeader,
+    payment: &ExecutableDeployItem,
+) -> Result<(), HeaderError> {
+    if header.ttl() == TimeDiff::from_seconds(0) {
+        return Err(HeaderError::ZeroTtl);
+    }
+    let gas_price = header.gas_price();
+    if gas_price == 0 {
+        return Err(HeaderError::ZeroGasPrice);
+    }
+    match system_payment_amount(payment) {
+        Some(payment) if payment < U512::from(gas_price) => {
+            Err(HeaderError::GasPriceAbovePayment { gas_price, payment })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns the amount of the system payment, if `payment` is one.
+fn system_payment_amount(payment: &ExecutableDeployItem) -> Option<U512> {
+    match payment {
+        ExecutableDeployItem::ModuleBytes { module_bytes, args }
+            if module_bytes.inner_bytes().is_empty() =>
+        {
+            args.get(ARG_AMOUNT)?.clone().into_t::<U512>().ok()
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use casper_node::types::Deploy;
+    use casper_types::{
+        bytesrepr::Bytes, runtime_args, AccessRights, AsymmetricType, PublicKey, RuntimeArgs,
+        SecretKey, Timestamp, URef,
+    };
+
+    use super::*;
+
+    #[test]
+    fn transfer_with_u64_amount_is_invalid() {
+        let args = runtime_args! {
+            "amount" => 10000u64,
+            "id" => Some(1u64),
+            "target" => URef::new([1u8; 32], AccessRights::READ_ADD),
+        };
+        assert_eq!(
+            validate_transfer_args(&args),
+            Err(ArgError::InvalidType {
+                name: ARG_AMOUNT,
+                expected: vec![CLType::U512],
+                found: CLType::U64,
+            })
+        );
+    }
+
+    #[test]
+    fn transfer_with_plain_u64_id_is_invalid() {
+        let args = runtime_args! {
+            "amount" => casper_types::U512::from(10000u64),
+            "id" => 1u64,
+            "target" => URef::new([1u8; 32], AccessRights::READ_ADD),
+        };
+        assert!(matches!(
+            validate_transfer_args(&args),
+            Err(ArgError::InvalidType { name: ARG_ID, .. })
+        ));
+    }
+
+    #[test]
+    fn delegation_requires_public_keys() {
+        let public_key = PublicKey::ed25519_from_bytes([1u8; 32]).unwrap();
+        let args = runtime_args! {
+            "delegator" => public_key,
+            "validator" => [3u8; 32],
+            "amount" => casper_types::U512::from(10000u64),
+        };
+        assert!(matches!(
+            validate_delegation_args(&args),
+            Err(ArgError::InvalidType {
+                name: VALIDATOR_ARG_KEY,
+                ..
+            })
+        ));
+        assert_eq!(
+            validate_redelegation_args(&RuntimeArgs::new()),
+            Err(ArgError::Missing {
+                name: DELEGATOR_ARG_KEY
+            })
+        );
+    }
+
+    #[test]
+    fn redelegation_requires_new_validator() {
+        let public_key = PublicKey::ed25519_from_bytes([1u8; 32]).unwrap();
+        let args = runtime_args! {
+            "delegator" => public_key.clone(),
+            "validator" => public_key,
+            "amount" => casper_types::U512::from(10000u64),
+        };
+        let redelegate = |entry_point: &str| ExecutableDeployItem::StoredContractByName {
+            name: "auction".to_string(),
+            entry_point: entry_point.to_string(),
+            args: args.clone(),
+        };
+        assert_eq!(
+            validate_session(&redelegate("redelegate")),
+            Err(ArgError::Missing {
+                name: NEW_VALIDATOR_ARG_KEY
+            })
+        );
+        // Other entry points may not need one.
+        assert_eq!(validate_session(&redelegate("delegate")), Ok(()));
+    }
+
+    #[test]
+    fn payment_amount_is_required_unless_transfer() {
+        let custom_payment = ExecutableDeployItem::StoredContractByName {
+            name: "payment_contract".to_string(),
+            entry_point: "pay".to_string(),
+            args: RuntimeArgs::new(),
+        };
+        let generic = ExecutableDeployItem::StoredContractByName {
+            name: "generic-contract".to_string(),
+            entry_point: "generic-txn-entrypoint".to_string(),
+            args: RuntimeArgs::new(),
+        };
+        assert_eq!(
+            validate_payment_amount(&custom_payment, &generic),
+            Err(ArgError::Missing { name: ARG_AMOUNT })
+        );
+        let transfer = ExecutableDeployItem::Transfer {
+            args: RuntimeArgs::new(),
+        };
+        assert_eq!(validate_payment_amount(&custom_payment, &transfer), Ok(()));
+    }
+
+    #[test]
+    fn transfer_is_session_only() {
+        let transfer = ExecutableDeployItem::Transfer {
+            args: RuntimeArgs::new(),
+        };
+        assert_eq!(
+            validate_placement(TxnPhase::Payment, &transfer),
+            Err(PlacementError::TransferAsPayment)
+        );
+        assert_eq!(validate_placement(TxnPhase::Session, &transfer), Ok(()));
+
+        let empty = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: RuntimeArgs::new(),
+        };
+        assert_eq!(validate_placement(TxnPhase::Payment, &empty), Ok(()));
+        assert_eq!(
+            validate_placement(TxnPhase::Session, &empty),
+            Err(PlacementError::EmptySessionModuleBytes)
+        );
+    }
+    #[test]
+    fn gas_price_above_system_payment_is_invalid() {
+        let payment = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: runtime_args! { "amount" => U512::from(10u64) },
+        };
+        let deploy = Deploy::new(
+            Timestamp::from_str("2021-05-04T14:20:35.104Z").unwrap(),
+            TimeDiff::from_seconds(60),
+            11,
+            vec![],
+            "mainnet".to_string(),
+            payment.clone(),
+            ExecutableDeployItem::Transfer {
+                args: RuntimeArgs::new(),
+            },
+            &SecretKey::ed25519_from_bytes([1u8; 32]).unwrap(),
+            None,
+        );
+        assert_eq!(
+            validate_header(deploy.header(), &payment),
+            Err(HeaderError::GasPriceAbovePayment {
+                gas_price: 11,
+                payment: U512::from(10u64),
+            })
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use crate::test_data::rng::StreamId;
+
 /// A generic wrapper around any type `T` that can be considered as being a sample test vector.
 /// It has associated `label` that described the sample and validity flag (`valid`)
 /// indicating whether the sample is correct - i.e. whether it is a valid CasperNetwork transaction.
@@ -6,6 +8,8 @@ pub(crate) struct Sample<V> {
     label: String,
     sample: V,
     valid: bool,
+    /// RNG stream the random parts of the sample were drawn from, if it has its own.
+    stream: Option<StreamId>,
 }
 
 impl<V> Sample<V> {
@@ -14,9 +18,22 @@ impl<V> Sample<V> {
             label: label.into(),
             sample,
             valid,
+            stream: None,
         }
     }
 
+    pub(crate) fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub(crate) fn sample(&self) -> &V {
+        &self.sample
+    }
+
+    pub(crate) fn valid(&self) -> bool {
+        self.valid
+    }
+
     pub(crate) fn destructure(self) -> (String, V, bool) {
         (self.label, self.sample, self.valid)
     }
@@ -24,4 +41,12 @@ impl<V> Sample<V> {
     pub(crate) fn add_label(&mut self, label: String) {
         self.label = format!("{}__{}", self.label, label);
     }
+
+    pub(crate) fn stream(&self) -> Option<&StreamId> {
+        self.stream.as_ref()
+    }
+
+    pub(crate) fn set_stream(&mut self, stream: StreamId) {
+        self.stream = Some(stream);
+    }
 }
@@ -1,40 +1,59 @@
-use std::str::FromStr;
+use std::{convert::TryFrom, str::FromStr};
 
 use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_hashing::Digest;
 use casper_node::types::{Deploy, DeployHash};
 use casper_types::{
-    account::AccountHash, AccessRights, AsymmetricType, CLValue, Key, PublicKey, RuntimeArgs,
-    SecretKey, TimeDiff, Timestamp, URef, U512,
+    account::AccountHash,
+    bytesrepr::{Bytes, ToBytes},
+    runtime_args, AccessRights, AsymmetricType, CLValue, Key, PublicKey, RuntimeArgs, SecretKey,
+    TimeDiff, Timestamp, URef, U512,
 };
 use rand::{prelude::*, Rng};
 
 use auction::{delegate, undelegate};
 
-use crate::sample::Sample;
+use crate::{
+    chainspec::ChainspecLimits,
+    ledger,
+    oracle::ValidityOracle,
+    parser::validation::{validate_header, validate_payment_amount},
+    sample::Sample,
+};
 
-use self::{auction::redelegate, commons::UREF_ADDR};
+use self::{
+    auction::redelegate,
+    combinations::{Combination, Coverage},
+    commons::{payment_sample, sample_executables, session_sample, UREF_ADDR},
+    rng::{StreamId, StreamSeed},
+};
 
+mod adversarial;
 mod auction;
+pub(crate) mod combinations;
 mod commons;
+mod custom_payment;
 mod generic;
 mod native_transfer;
+mod random_args;
+pub(crate) mod rng;
 pub(crate) mod sign_message;
+mod spec;
 mod system_payment;
 
-// From the chainspec.
-// 1 minute.
+// Shortest TTL we generate samples with - 1 minute.
 const MIN_TTL: TimeDiff = TimeDiff::from_seconds(60);
-// 1 day.
-const MAX_TTL: TimeDiff = TimeDiff::from_seconds(60 * 60 * 24);
 // 1 hour.
 const TTL_HOUR: TimeDiff = TimeDiff::from_seconds(60 * 60);
 
-// From the chainspec.
+// Deploy doesn't have to depend on any other deploy.
 const MIN_DEPS_COUNT: u8 = 0;
-const MAX_DEPS_COUNT: u8 = 10;
 
-// From the chainspec.
+// Deploy has to be signed at least by the account's key.
 const MIN_APPROVALS_COUNT: u8 = 1;
+// Samples are signed by at most this many keys, even if the chainspec allows more -
+// a signature per key would blow up the test vectors. Past this count only the number
+// of approvals is displayed anyway.
 const MAX_APPROVALS_COUNT: u8 = 10;
 
 /// Represents native transfer sample.
@@ -123,7 +142,11 @@ impl TransferTarget {
     }
 
     fn uref() -> TransferTarget {
-        let uref = URef::new(UREF_ADDR, AccessRights::READ_ADD_WRITE);
+        Self::uref_with_access_rights(AccessRights::READ_ADD_WRITE)
+    }
+
+    fn uref_with_access_rights(access_rights: AccessRights) -> TransferTarget {
+        let uref = URef::new(UREF_ADDR, access_rights);
         TransferTarget::URef(uref)
     }
 
@@ -173,52 +196,112 @@ impl TransferTarget {
     }
 }
 
-/// Returns a sample `Deploy`, given the input data.
+// Default timestamp of the sample deploys.
+const TIMESTAMP: &str = "2021-05-04T14:20:35.104Z";
+
+// Timestamps within the first day since the Unix epoch are considered epoch-near.
+const EPOCH_NEAR_TIMESTAMP_MAX: u64 = 24 * 60 * 60 * 1000;
+// Far-future timestamps are between 2100-01-01T00:00:00Z
+const FAR_FUTURE_TIMESTAMP_MIN: u64 = 4_102_444_800_000;
+// and 9999-12-31T23:59:59.999Z - the last one that can be displayed in RFC 3339 format.
+const FAR_FUTURE_TIMESTAMP_MAX: u64 = 253_402_300_799_999;
+
+// Amount of the sample native transfers - the minimum the bundled chainspec allows to transfer.
+const TRANSFER_AMOUNT: u64 = 2_500_000_000;
+
+// Default gas price of the sample deploys.
+const GAS_PRICE: u64 = 2;
+
+// Ledger displays this many characters of a value on a single page - both rows of the value.
+const LEDGER_PAGE_CHAR_COUNT: usize =
+    ledger::LEDGER_VIEW_TOP_ROW_CHAR_COUNT + ledger::LEDGER_VIEW_BOTTOM_CHAR_COUNT;
+
+// Characters of the non-ASCII chain names.
+const NON_ASCII_CHARS: [char; 8] = ['é', 'ü', 'ß', 'Ω', 'ж', '中', 'ネ', '€'];
+
+/// Header fields of a sample deploy. Its account is the first of the signing keys.
+#[derive(Clone, Debug)]
+struct HeaderParams {
+    timestamp: Timestamp,
+    ttl: TimeDiff,
+    gas_price: u64,
+    dependencies: Vec<DeployHash>,
+    chain_name: String,
+}
+
+impl HeaderParams {
+    fn new(ttl: TimeDiff, dependencies: Vec<DeployHash>, chain_name: &str) -> Self {
+        HeaderParams {
+            timestamp: Timestamp::from_str(TIMESTAMP).unwrap(),
+            ttl,
+            gas_price: GAS_PRICE,
+            dependencies,
+            chain_name: chain_name.to_string(),
+        }
+    }
+
+    fn with_gas_price(mut self, gas_price: u64) -> Self {
+        self.gas_price = gas_price;
+        self
+    }
+
+    fn with_timestamp(mut self, timestamp: Timestamp) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+}
+
+/// Returns a sample `Deploy`, given the input data. It's valid if both of its items and its header are,
+/// and it doesn't exceed any of the `chainspec` limits.
 fn make_deploy_sample(
     session: Sample<ExecutableDeployItem>,
     payment: Sample<ExecutableDeployItem>,
-    ttl: TimeDiff,
-    dependencies: Vec<DeployHash>,
+    header: HeaderParams,
     signing_keys: &[SecretKey],
+    chainspec: &ChainspecLimits,
 ) -> Sample<Deploy> {
     let (main_key, secondary_keys) = signing_keys.split_at(1);
     let (payment_label, payment, payment_validity) = payment.destructure();
     let (session_label, session, session_validity) = session.destructure();
 
-    let deploy = Deploy::new(
-        Timestamp::from_str("2021-05-04T14:20:35.104Z").unwrap(),
-        ttl,
-        2,
-        dependencies,
-        String::from("mainnet"),
+    let mut deploy = Deploy::new(
+        header.timestamp,
+        header.ttl,
+        header.gas_price,
+        header.dependencies,
+        header.chain_name,
         payment,
         session,
         &main_key[0],
         None,
     );
 
-    let mut sample = Sample::new(session_label, deploy, session_validity && payment_validity);
-    sample.add_label(payment_label);
-
     // Sign deploy with possibly multiple keys.
     for key in secondary_keys {
-        let (label, mut deploy, validity) = sample.destructure();
         deploy.sign(key);
-        sample = Sample::new(label, deploy, validity);
     }
+
+    let valid = session_validity
+        && payment_validity
+        && validate_payment_amount(deploy.payment(), deploy.session()).is_ok()
+        && validate_header(deploy.header(), deploy.payment()).is_ok()
+        && chainspec.check(&deploy).is_ok();
+    let mut sample = Sample::new(session_label, deploy, valid);
+    sample.add_label(payment_label);
     sample
 }
 
-fn make_dependencies(count: u8) -> Vec<DeployHash> {
-    if count == 0 {
-        return vec![];
-    }
+/// Drops all the approvals of the deploy - `Deploy::new` always signs it with the account's key.
+fn without_approvals(deploy: Deploy) -> Deploy {
+    let mut json = serde_json::to_value(&deploy).expect("deploy should serialize to JSON");
+    json["approvals"] = serde_json::Value::Array(vec![]);
+    serde_json::from_value(json).expect("deploy without approvals should deserialize")
+}
 
-    let mut dependencies = vec![];
-    for i in 0..count {
-        dependencies.push(DeployHash::new([i; 32].into()));
-    }
-    dependencies
+fn make_dependencies<R: Rng>(rng: &mut R, count: u8) -> Vec<DeployHash> {
+    (0..count)
+        .map(|_| DeployHash::new(rng.gen::<[u8; 32]>().into()))
+        .collect()
 }
 
 fn random_keys(key_count: u8) -> Vec<SecretKey> {
@@ -234,96 +317,225 @@ fn random_keys(key_count: u8) -> Vec<SecretKey> {
     out
 }
 
+/// Returns an epoch-near, the default and a far-future timestamp.
+fn sample_timestamps<R: Rng>(rng: &mut R) -> [Timestamp; 3] {
+    [
+        Timestamp::from(rng.gen_range(0..=EPOCH_NEAR_TIMESTAMP_MAX)),
+        Timestamp::from_str(TIMESTAMP).unwrap(),
+        Timestamp::from(rng.gen_range(FAR_FUTURE_TIMESTAMP_MIN..=FAR_FUTURE_TIMESTAMP_MAX)),
+    ]
+}
+
+/// Returns labeled chain names the samples are rendered with: the one from the chainspec, names of its
+/// test networks filling exactly one Ledger page and spilling over to the next one, and a non-ASCII one.
+///
+/// Ledger can't tell which network a deploy is meant for, so the chain name doesn't change validity -
+/// the validity oracle accepts all of these names.
+pub(crate) fn sample_chain_names(chainspec: &ChainspecLimits) -> [(String, String); 4] {
+    let chain_name = chainspec.chain_name();
+    // The chain name followed by a dash and the alphabet, as many times as needed.
+    let ascii_name = |len: usize| -> String {
+        chain_name
+            .chars()
+            .chain(std::iter::once('-'))
+            .chain(('a'..='z').cycle())
+            .take(len)
+            .collect()
+    };
+    let one_page = ascii_name(LEDGER_PAGE_CHAR_COUNT);
+    let two_pages = ascii_name(LEDGER_PAGE_CHAR_COUNT + 1);
+    let non_ascii: String = NON_ASCII_CHARS.iter().collect();
+    [
+        ("chain_chainspec".to_string(), chain_name.to_string()),
+        (format!("chain_{}_chars", one_page.len()), one_page),
+        (format!("chain_{}_chars", two_pages.len()), two_pages),
+        (
+            "chain_non_ascii".to_string(),
+            format!("{}-{}", chain_name, non_ascii),
+        ),
+    ]
+}
+
+/// Identifies the RNG stream of the deploy sample built from `session` and `payment`.
+///
+/// Labels alone aren't unique - e.g. native transfers that differ only in the amount share them -
+/// so the stream is identified by the digest of the items as well.
+fn stream_id(
+    session: &Sample<ExecutableDeployItem>,
+    payment: &Sample<ExecutableDeployItem>,
+) -> String {
+    let items = [session.sample(), payment.sample()]
+        .iter()
+        .flat_map(|item| item.to_bytes().expect("item should serialize"))
+        .collect::<Vec<u8>>();
+    let digest = Digest::hash(items);
+    format!(
+        "{}__{}__{}",
+        session.label(),
+        payment.label(),
+        hex::encode(&digest.value()[..8])
+    )
+}
+
 // Given input collections for session samples and payment samples,
 // returns a combination of all - every session samples is matched with every payment sample,
 // creating n^2 deploy samples.
 fn construct_samples<R: Rng>(
     rng: &mut R,
+    chainspec: &ChainspecLimits,
     session_samples: Vec<Sample<ExecutableDeployItem>>,
     payment_samples: Vec<Sample<ExecutableDeployItem>>,
 ) -> Vec<Sample<Deploy>> {
     let mut samples = vec![];
 
     // These params do not change validity of a sample.
-    let mut ttls = [MIN_TTL, TTL_HOUR, MAX_TTL];
-    let mut deps_count = [MIN_DEPS_COUNT, 3, MAX_DEPS_COUNT];
-    let mut key_count = [MIN_APPROVALS_COUNT, 3, MAX_APPROVALS_COUNT];
+    // Boundaries follow the chainspec, mid values are capped in case they're out of its bounds.
+    // Key count is capped at `MAX_APPROVALS_COUNT` as well.
+    let max_ttl = chainspec.max_ttl();
+    let max_deps_count = chainspec.max_dependencies();
+    let max_approvals_count = chainspec.max_approvals().min(MAX_APPROVALS_COUNT);
+    let ttls = [MIN_TTL, TTL_HOUR.min(max_ttl), max_ttl];
+    let deps_count = [MIN_DEPS_COUNT, 3u8.min(max_deps_count), max_deps_count];
+    let key_count = [
+        MIN_APPROVALS_COUNT,
+        3u8.min(max_approvals_count),
+        max_approvals_count,
+    ];
+    // Gas price extremes that still leave the system payment some gas to buy.
+    let gas_prices = [1, GAS_PRICE, system_payment::PAYMENT_AMOUNT];
+
+    // Every sample draws from its own stream, identified by its label and items,
+    // so that adding or removing a sample doesn't change the random values of the others.
+    let streams = StreamSeed::new(rng);
 
     for session in session_samples {
         for payment in &payment_samples {
+            let stream_id = stream_id(&session, payment);
+            let mut rng = streams.stream(&stream_id);
+            let rng = &mut rng;
+
             // Random number of keys.
-            key_count.shuffle(rng);
-            // Random signing keys count.
-            let mut keys: Vec<SecretKey> = random_keys(*key_count.first().unwrap());
+            let mut keys: Vec<SecretKey> = random_keys(*key_count.choose(rng).unwrap());
             // Randomize order of keys, so that both alg have chance to be the main one.
             keys.shuffle(rng);
 
             // Random dependencies within correct limits.
-            deps_count.shuffle(rng);
-            let dependencies = make_dependencies(deps_count.first().cloned().unwrap());
+            let dependencies_count = *deps_count.choose(rng).unwrap();
+            let dependencies = make_dependencies(rng, dependencies_count);
 
             // Pick a random TTL value.
-            ttls.shuffle(rng);
-            let ttl = ttls.first().cloned().unwrap();
-
-            let sample_deploy =
-                make_deploy_sample(session.clone(), payment.clone(), ttl, dependencies, &keys);
+            let ttl = *ttls.choose(rng).unwrap();
+
+            // Pick random header fields and record them in the label.
+            let timestamp = *sample_timestamps(rng).choose(rng).unwrap();
+            let gas_price = *gas_prices.choose(rng).unwrap();
+            let (chain_label, chain_name) =
+                sample_chain_names(chainspec).choose(rng).cloned().unwrap();
+
+            let header = HeaderParams::new(ttl, dependencies, &chain_name)
+                .with_timestamp(timestamp)
+                .with_gas_price(gas_price);
+            let mut sample_deploy =
+                make_deploy_sample(session.clone(), payment.clone(), header, &keys, chainspec);
+            sample_deploy.add_label(format!("ts_{}", timestamp.millis()));
+            sample_deploy.add_label(format!("gas_{}", gas_price));
+            sample_deploy.add_label(chain_label);
+            sample_deploy.set_stream(StreamId::new(streams, stream_id));
             samples.push(sample_deploy);
         }
     }
     samples
 }
 
-pub(crate) fn redelegate_samples<R: Rng>(rng: &mut R) -> Vec<Sample<Deploy>> {
+pub(crate) fn redelegate_samples<R: Rng>(
+    rng: &mut R,
+    chainspec: &ChainspecLimits,
+) -> Vec<Sample<Deploy>> {
     let valid_samples = redelegate::valid();
     let valid_payment_samples = vec![system_payment::valid()];
 
-    let mut samples = construct_samples(rng, valid_samples, valid_payment_samples);
+    let mut samples = construct_samples(rng, chainspec, valid_samples, valid_payment_samples);
     let invalid_samples = redelegate::invalid();
     let invalid_payment_samples = vec![system_payment::invalid(), system_payment::valid()];
     samples.extend(construct_samples(
         rng,
+        chainspec,
         invalid_samples,
         invalid_payment_samples,
     ));
     samples
 }
 
-pub(crate) fn generic_samples<R: Rng>(rng: &mut R) -> Vec<Sample<Deploy>> {
-    let valid_samples = generic::valid(rng);
+/// Returns generic contract calls, with composite args nested up to `composite_depth`.
+pub(crate) fn generic_samples<R: Rng>(
+    rng: &mut R,
+    chainspec: &ChainspecLimits,
+    composite_depth: usize,
+) -> Vec<Sample<Deploy>> {
+    let valid_samples = generic::valid(rng, composite_depth, chainspec.session_args_max_length());
     let valid_payment_samples = vec![system_payment::valid()];
 
-    let mut samples = construct_samples(rng, valid_samples.clone(), valid_payment_samples);
+    let mut samples =
+        construct_samples(rng, chainspec, valid_samples.clone(), valid_payment_samples);
 
     // Generic transactions are invalid only if their payment contract is invalid.
     // Otherwise there are no rules that could be violated and make txn invalid -
     // if it has correct structure it's valid b/c we don't know what the contracts expect.
     samples.extend(construct_samples(
         rng,
+        chainspec,
         valid_samples,
         vec![system_payment::invalid()],
     ));
     samples
 }
 
-pub(crate) fn native_transfer_samples<R: Rng>(rng: &mut R) -> Vec<Sample<Deploy>> {
+/// Returns native transfer samples, with valid ones combined by the `combination` strategy,
+/// and the coverage of their combinations.
+pub(crate) fn native_transfer_samples<R: Rng>(
+    rng: &mut R,
+    chainspec: &ChainspecLimits,
+    combination: Combination,
+) -> (Vec<Sample<Deploy>>, Coverage) {
+    let (valid_samples, coverage) = native_transfer::valid(combination);
     let mut native_transfer_samples =
-        construct_samples(rng, native_transfer::valid(), vec![system_payment::valid()]);
+        construct_samples(rng, chainspec, valid_samples, vec![system_payment::valid()]);
 
     native_transfer_samples.extend(construct_samples(
         rng,
+        chainspec,
         native_transfer::invalid(),
         vec![system_payment::invalid(), system_payment::valid()],
     ));
-    native_transfer_samples
+    (native_transfer_samples, coverage)
 }
 
-pub(crate) fn delegate_samples<R: Rng>(rng: &mut R) -> Vec<Sample<Deploy>> {
-    let mut delegate_samples =
-        construct_samples(rng, delegate::valid(), vec![system_payment::valid()]);
+pub(crate) fn access_rights_samples<R: Rng>(
+    rng: &mut R,
+    chainspec: &ChainspecLimits,
+) -> Vec<Sample<Deploy>> {
+    construct_samples(
+        rng,
+        chainspec,
+        native_transfer::access_rights(),
+        vec![system_payment::valid()],
+    )
+}
+
+pub(crate) fn delegate_samples<R: Rng>(
+    rng: &mut R,
+    chainspec: &ChainspecLimits,
+) -> Vec<Sample<Deploy>> {
+    let mut delegate_samples = construct_samples(
+        rng,
+        chainspec,
+        delegate::valid(),
+        vec![system_payment::valid()],
+    );
 
     delegate_samples.extend(construct_samples(
         rng,
+        chainspec,
         delegate::invalid(),
         vec![system_payment::invalid(), system_payment::valid()],
     ));
@@ -331,15 +543,370 @@ pub(crate) fn delegate_samples<R: Rng>(rng: &mut R) -> Vec<Sample<Deploy>> {
     delegate_samples
 }
 
-pub(crate) fn undelegate_samples<R: Rng>(rng: &mut R) -> Vec<Sample<Deploy>> {
-    let mut undelegate_samples =
-        construct_samples(rng, undelegate::valid(), vec![system_payment::valid()]);
+pub(crate) fn undelegate_samples<R: Rng>(
+    rng: &mut R,
+    chainspec: &ChainspecLimits,
+) -> Vec<Sample<Deploy>> {
+    let mut undelegate_samples = construct_samples(
+        rng,
+        chainspec,
+        undelegate::valid(),
+        vec![system_payment::valid()],
+    );
 
     undelegate_samples.extend(construct_samples(
         rng,
+        chainspec,
         undelegate::invalid(),
         vec![system_payment::invalid(), system_payment::valid()],
     ));
 
     undelegate_samples
 }
+
+/// Returns native transfers signed by exactly 1, 3 and maximum number of keys allowed by the chainspec
+/// (capped at `MAX_APPROVALS_COUNT`), so that both listing the approvals' signers and the fallback
+/// to the approvals count are covered.
+pub(crate) fn approvals_samples(chainspec: &ChainspecLimits) -> Vec<Sample<Deploy>> {
+    let transfer = NativeTransfer::new(
+        TransferTarget::key(),
+        U512::from(TRANSFER_AMOUNT),
+        1,
+        TransferSource::none(),
+    );
+    let session = session_sample(
+        "native_transfer",
+        ExecutableDeployItem::Transfer {
+            args: transfer.into(),
+        },
+    );
+
+    let max_approvals_count = chainspec.max_approvals().min(MAX_APPROVALS_COUNT);
+    [
+        MIN_APPROVALS_COUNT,
+        3u8.min(max_approvals_count),
+        max_approvals_count,
+    ]
+    .iter()
+    .map(|&key_count| {
+        let keys = random_keys(key_count);
+        let mut sample = make_deploy_sample(
+            session.clone(),
+            system_payment::valid(),
+            HeaderParams::new(TTL_HOUR, vec![], chainspec.chain_name()),
+            &keys,
+            chainspec,
+        );
+        sample.add_label(format!("approvals_{}", key_count));
+        sample
+    })
+    .collect()
+}
+
+/// Returns native transfers of amounts that are interesting to display in CSPR units:
+/// zero, a single mote, whole and fractional CSPR and the maximum `U512` value.
+/// The ones below the chainspec's minimum transfer amount are invalid.
+pub(crate) fn amount_samples(chainspec: &ChainspecLimits) -> Vec<Sample<Deploy>> {
+    let amounts = [
+        ("zero", U512::zero()),
+        ("one_mote", U512::one()),
+        ("one_cspr", U512::from(1_000_000_000u64)),
+        ("fractional_cspr", U512::from(1_500_000_000u64)),
+        ("thousands_cspr", U512::from(1_234_567_000_000_001u64)),
+        ("max", U512::MAX),
+    ];
+    let keys = random_keys(1);
+
+    amounts
+        .iter()
+        .map(|(label, amount)| {
+            let transfer =
+                NativeTransfer::new(TransferTarget::key(), *amount, 1, TransferSource::none());
+            let session = session_sample(
+                format!("native_transfer_amount_{}", label),
+                ExecutableDeployItem::Transfer {
+                    args: transfer.into(),
+                },
+            );
+            make_deploy_sample(
+                session,
+                system_payment::valid(),
+                HeaderParams::new(TTL_HOUR, vec![], chainspec.chain_name()),
+                &keys,
+                chainspec,
+            )
+        })
+        .collect()
+}
+
+/// Returns a native transfer and a generic contract call, each paid by every kind of custom payment:
+/// custom wasm and calls to stored payment contracts, with and without `amount`.
+pub(crate) fn custom_payment_samples<R: Rng>(
+    rng: &mut R,
+    chainspec: &ChainspecLimits,
+) -> Vec<Sample<Deploy>> {
+    let transfer = NativeTransfer::new(
+        TransferTarget::key(),
+        U512::from(TRANSFER_AMOUNT),
+        1,
+        TransferSource::none(),
+    );
+    let sessions = vec![
+        session_sample(
+            "native_transfer",
+            ExecutableDeployItem::Transfer {
+                args: transfer.into(),
+            },
+        ),
+        session_sample(
+            "generic",
+            ExecutableDeployItem::StoredContractByName {
+                name: "generic-contract".to_string(),
+                entry_point: "generic-txn-entrypoint".to_string(),
+                args: RuntimeArgs::new(),
+            },
+        ),
+    ];
+    construct_samples(rng, chainspec, sessions, custom_payment::samples())
+}
+
+/// Returns generic contract calls with random args of random types, nested up to a few levels,
+/// by each of the stored contract variants.
+pub(crate) fn random_args_samples<R: Rng>(
+    rng: &mut R,
+    chainspec: &ChainspecLimits,
+) -> Vec<Sample<Deploy>> {
+    let sessions = random_args::random_args(rng, chainspec.session_args_max_length())
+        .into_iter()
+        .enumerate()
+        .flat_map(|(idx, args)| {
+            sample_executables(
+                "generic-txn-entrypoint",
+                args,
+                Some(format!("random_args_{}", idx)),
+            )
+        })
+        .collect();
+    construct_samples(rng, chainspec, sessions, vec![system_payment::valid()])
+}
+
+/// Returns deploys trying to confuse the user about what they approve, paid by the system payment:
+/// see `adversarial::samples`.
+pub(crate) fn adversarial_samples<R: Rng>(
+    rng: &mut R,
+    chainspec: &ChainspecLimits,
+) -> Vec<Sample<Deploy>> {
+    construct_samples(
+        rng,
+        chainspec,
+        adversarial::samples(),
+        vec![system_payment::valid()],
+    )
+}
+
+/// Returns deploys with an item placed in a phase it can't be executed in: a native transfer as
+/// the payment and empty module bytes as the session. All of them are invalid.
+pub(crate) fn placement_violation_samples<R: Rng>(
+    rng: &mut R,
+    chainspec: &ChainspecLimits,
+) -> Vec<Sample<Deploy>> {
+    let transfer = NativeTransfer::new(
+        TransferTarget::key(),
+        U512::from(TRANSFER_AMOUNT),
+        1,
+        TransferSource::none(),
+    );
+    let transfer_session = session_sample(
+        "native_transfer",
+        ExecutableDeployItem::Transfer {
+            args: transfer.clone().into(),
+        },
+    );
+    let transfer_payment = payment_sample(
+        "payment_transfer",
+        ExecutableDeployItem::Transfer {
+            args: transfer.into(),
+        },
+    );
+    let empty_session = session_sample(
+        "session_module_bytes_empty",
+        ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: RuntimeArgs::new(),
+        },
+    );
+
+    let mut samples = construct_samples(
+        rng,
+        chainspec,
+        vec![transfer_session],
+        vec![transfer_payment.clone()],
+    );
+    samples.extend(construct_samples(
+        rng,
+        chainspec,
+        vec![empty_session],
+        vec![system_payment::valid(), transfer_payment],
+    ));
+    samples
+}
+
+/// Returns deploys breaking one of the limits of the chainspec or the header rules, each labeled
+/// with the rule it breaks: TTL, dependencies, approvals and payment or session args above
+/// the chainspec maximum, zero TTL, no approvals, zero gas price or one the payment can't buy any gas with,
+/// and a chain name of none of the networks the samples are rendered with.
+/// All of them are invalid - panics if the validity oracle accepts any of them.
+///
+/// The maximum deploy size isn't covered - a deploy above it would ship a multi-megabyte blob.
+pub(crate) fn header_violation_samples<R: Rng>(
+    rng: &mut R,
+    chainspec: &ChainspecLimits,
+) -> Vec<Sample<Deploy>> {
+    let session = session_sample(
+        "header_violation",
+        ExecutableDeployItem::StoredContractByName {
+            name: "generic-contract".to_string(),
+            entry_point: "generic-txn-entrypoint".to_string(),
+            args: RuntimeArgs::new(),
+        },
+    );
+    let payment = system_payment::valid();
+    let chain_name = chainspec.chain_name();
+    let header = HeaderParams::new(TTL_HOUR, vec![], chain_name);
+    // Args padded with a byte array as long as the limit itself, so that they exceed it.
+    let padding = |max_length: u32| Bytes::from(vec![0u8; max_length as usize]);
+
+    let mut violations = vec![
+        (
+            "ttl_above_max",
+            session.clone(),
+            payment.clone(),
+            HeaderParams::new(
+                TimeDiff::from_millis(chainspec.max_ttl().millis() + 1),
+                vec![],
+                chain_name,
+            ),
+            MIN_APPROVALS_COUNT,
+        ),
+        (
+            "ttl_below_min",
+            session.clone(),
+            payment.clone(),
+            HeaderParams::new(TimeDiff::from_seconds(0), vec![], chain_name),
+            MIN_APPROVALS_COUNT,
+        ),
+        (
+            "chain_name_mismatch",
+            session.clone(),
+            payment.clone(),
+            HeaderParams::new(TTL_HOUR, vec![], &format!("{}-other", chain_name)),
+            MIN_APPROVALS_COUNT,
+        ),
+        (
+            "gas_price_zero",
+            session.clone(),
+            payment.clone(),
+            header.clone().with_gas_price(0),
+            MIN_APPROVALS_COUNT,
+        ),
+        (
+            "gas_price_above_payment",
+            session.clone(),
+            payment.clone(),
+            header
+                .clone()
+                .with_gas_price(system_payment::PAYMENT_AMOUNT + 1),
+            MIN_APPROVALS_COUNT,
+        ),
+        (
+            "session_args_above_max",
+            session_sample(
+                "header_violation",
+                ExecutableDeployItem::StoredContractByName {
+                    name: "generic-contract".to_string(),
+                    entry_point: "generic-txn-entrypoint".to_string(),
+                    args: runtime_args! {
+                        "padding" => padding(chainspec.session_args_max_length())
+                    },
+                },
+            ),
+            payment.clone(),
+            header.clone(),
+            MIN_APPROVALS_COUNT,
+        ),
+        (
+            "payment_args_above_max",
+            session.clone(),
+            payment_sample(
+                "payment_system",
+                ExecutableDeployItem::ModuleBytes {
+                    module_bytes: Bytes::new(),
+                    args: runtime_args! {
+                        "amount" => U512::from(system_payment::PAYMENT_AMOUNT),
+                        "padding" => padding(chainspec.payment_args_max_length())
+                    },
+                },
+            ),
+            header.clone(),
+            MIN_APPROVALS_COUNT,
+        ),
+    ];
+    // Limits at the maximum of their type can't be exceeded.
+    if let Some(deps_count) = chainspec.max_dependencies().checked_add(1) {
+        violations.push((
+            "deps_above_max",
+            session.clone(),
+            payment.clone(),
+            HeaderParams::new(TTL_HOUR, make_dependencies(rng, deps_count), chain_name),
+            MIN_APPROVALS_COUNT,
+        ));
+    }
+    if let Some(key_count) = chainspec
+        .max_associated_keys()
+        .checked_add(1)
+        .and_then(|count| u8::try_from(count).ok())
+    {
+        violations.push((
+            "approvals_above_max",
+            session.clone(),
+            payment.clone(),
+            header.clone(),
+            key_count,
+        ));
+    }
+
+    let oracle = ValidityOracle::new(chainspec);
+    // A sample the oracle accepts doesn't break the rule it's labeled with.
+    let invalid = |label: String, deploy: Deploy| {
+        assert!(
+            oracle.validate(&deploy).is_err(),
+            "{} passes all the checks",
+            label
+        );
+        Sample::new(label, deploy, false)
+    };
+    let mut samples: Vec<Sample<Deploy>> = violations
+        .into_iter()
+        .map(|(rule, session, payment, header, key_count)| {
+            let keys = random_keys(key_count);
+            let (label, deploy, _) =
+                make_deploy_sample(session, payment, header, &keys, chainspec).destructure();
+            invalid(format!("{}__{}", label, rule), deploy)
+        })
+        .collect();
+
+    let keys = random_keys(MIN_APPROVALS_COUNT);
+    let (label, deploy, _) =
+        make_deploy_sample(session, payment, header, &keys, chainspec).destructure();
+    samples.push(invalid(
+        format!("{}__approvals_zero", label),
+        without_approvals(deploy),
+    ));
+    samples
+}
+
+/// Returns the samples described by the declarative specs (see `CL_SPECS`),
+/// grouped by the name of the spec file they come from.
+pub(crate) fn spec_samples(chainspec: &ChainspecLimits) -> Vec<(String, Vec<Sample<Deploy>>)> {
+    spec::load(chainspec)
+}
@@ -0,0 +1,297 @@
+//! Samples trying to trick the user into approving something else than what they think they approve:
+//! contract names posing as deploy types or keys, strings posing as amounts, keys and hashes,
+//! Unicode homoglyphs, control and invisible characters, and names long enough to push
+//! the values that follow them onto later pages.
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_types::{runtime_args, PublicKey, RuntimeArgs, SecretKey, U512};
+
+use crate::{sample::Sample, utils::parse_public_key};
+
+use super::{
+    commons::session_sample, NativeTransfer, TransferSource, TransferTarget, TRANSFER_AMOUNT,
+};
+
+const ENTRYPOINT: &str = "generic-txn-entrypoint";
+const CONTRACT_NAME: &str = "generic-contract";
+const AMOUNT: u64 = 1_000_000_000;
+/// Number of pages the long contract name spans, before its amount is displayed.
+const LONG_NAME_PAGES: usize = 4;
+
+/// Key of the account the user might take the contract name or the string args for.
+fn impersonated_key() -> PublicKey {
+    let secret_key = SecretKey::ed25519_from_bytes([7u8; 32]).expect("successful key construction");
+    PublicKey::from(&secret_key)
+}
+
+/// Contract names posing as something else, each with the label of what it poses as.
+fn confusing_names() -> Vec<(&'static str, String)> {
+    vec![
+        // Names of the deploy types, displayed right below the `Type` page.
+        ("delegate", "Delegate".to_string()),
+        ("token_transfer", "Token transfer".to_string()),
+        // Looks like the rendered public key.
+        ("public_key", parse_public_key(&impersonated_key())),
+        // "Delegate" with the Cyrillic 'е' (U+0435) instead of the Latin 'e'.
+        ("homoglyph", "D\u{0435}legate".to_string()),
+        // "pay" spelled entirely with the Cyrillic lookalikes.
+        (
+            "whole_script_homoglyph",
+            "\u{0440}\u{0430}\u{0443}".to_string(),
+        ),
+        // "Token transfer", reversed and displayed right-to-left by the U+202E override.
+        (
+            "right_to_left_override",
+            "\u{202E}refsnart nekoT".to_string(),
+        ),
+        // "Token transfer" with the zero-width space (U+200B) instead of the regular one.
+        ("zero_width_space", "Token\u{200B}transfer".to_string()),
+        // A control character, an emoji and a combining mark (U+0301, the acute accent over 'e').
+        ("newline", "Delegate\nToken transfer".to_string()),
+        ("emoji", "Token transfer \u{1F680}".to_string()),
+        ("combining_mark", "De\u{0301}legate".to_string()),
+        // Looks like the escaped zero-width space, but it's plain ASCII.
+        ("fake_escape", "Token\\u{200b}transfer".to_string()),
+        // Pushes the amount that follows it a few pages further.
+        (
+            "long",
+            "a".repeat(LONG_NAME_PAGES * super::LEDGER_PAGE_CHAR_COUNT),
+        ),
+    ]
+}
+
+/// Returns the adversarial session samples.
+///
+/// Contract names and args of generic contract calls can be anything, so all of them are valid -
+/// the names are displayed as they are (with the characters the device can't display escaped),
+/// it's the app that has to defend against them.
+/// Native transfers with a string target are invalid,
+/// even though their target is displayed just like a valid one.
+pub(super) fn samples() -> Vec<Sample<ExecutableDeployItem>> {
+    let mut samples: Vec<Sample<ExecutableDeployItem>> = confusing_names()
+        .into_iter()
+        .map(|(label, name)| {
+            session_sample(
+                format!("adversarial_name_{}", label),
+                ExecutableDeployItem::StoredContractByName {
+                    name,
+                    entry_point: ENTRYPOINT.to_string(),
+                    args: runtime_args! {
+                        "amount" => U512::from(AMOUNT)
+                    },
+                },
+            )
+        })
+        .collect();
+
+    // `String` amount is displayed as it is, so it can pose as the formatted motes.
+    samples.push(session_sample(
+        "adversarial_amount_string_motes",
+        ExecutableDeployItem::StoredContractByName {
+            name: CONTRACT_NAME.to_string(),
+            entry_point: ENTRYPOINT.to_string(),
+            args: runtime_args! {
+                "amount" => "1 000 000 000 motes".to_string()
+            },
+        },
+    ));
+
+    // The same, with the Cyrillic 'о' (U+043E) in "motes".
+    samples.push(session_sample(
+        "adversarial_amount_string_homoglyph",
+        ExecutableDeployItem::StoredContractByName {
+            name: CONTRACT_NAME.to_string(),
+            entry_point: ENTRYPOINT.to_string(),
+            args: runtime_args! {
+                "amount" => "1 000 000 000 m\u{043E}tes".to_string()
+            },
+        },
+    ));
+
+    // Delegation to a contract named "auction" with the Cyrillic 'а' (U+0430), put into the user's named keys.
+    // Its name is displayed in expert mode only, like for any other delegation.
+    samples.push(session_sample(
+        "adversarial_delegate_name_homoglyph",
+        ExecutableDeployItem::StoredContractByName {
+            name: "\u{0430}uction".to_string(),
+            entry_point: "delegate".to_string(),
+            args: runtime_args! {
+                "delegator" => impersonated_key(),
+                "validator" => PublicKey::from(&SecretKey::ed25519_from_bytes([3u8; 32]).unwrap()),
+                "amount" => U512::from(AMOUNT)
+            },
+        },
+    ));
+
+    // Recipient isn't validated, so it can be anyone else than the target.
+    let mut recipient_mismatch: RuntimeArgs = NativeTransfer::new(
+        TransferTarget::key(),
+        U512::from(TRANSFER_AMOUNT),
+        1,
+        TransferSource::none(),
+    )
+    .into();
+    recipient_mismatch
+        .insert("to", Some(impersonated_key().to_account_hash()))
+        .unwrap();
+    samples.push(session_sample(
+        "adversarial_transfer_recipient_mismatch",
+        ExecutableDeployItem::Transfer {
+            args: recipient_mismatch,
+        },
+    ));
+
+    // Strings formatted like keys are displayed just like the keys.
+    let string_targets = [
+        ("public_key", parse_public_key(&impersonated_key())),
+        (
+            "account_hash",
+            impersonated_key().to_account_hash().to_formatted_string(),
+        ),
+    ];
+    for (label, target) in string_targets {
+        samples.push(session_sample(
+            format!("adversarial_transfer_target_string_{}", label),
+            ExecutableDeployItem::Transfer {
+                args: runtime_args! {
+                    "amount" => U512::from(TRANSFER_AMOUNT),
+                    "id" => Some(1u64),
+                    "target" => target
+                },
+            },
+        ));
+    }
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use casper_types::TimeDiff;
+
+    use crate::{
+        chainspec::ChainspecLimits,
+        ledger::Element,
+        parser::{self, config::ParserConfig},
+        test_data::{make_deploy_sample, random_keys, system_payment, HeaderParams},
+        utils::parse_public_key,
+    };
+
+    use super::{confusing_names, impersonated_key, samples};
+
+    /// Regular (non-expert) elements of the sample's deploy, by label.
+    fn regular_elements(label: &str) -> (bool, Vec<Element>) {
+        let session = samples()
+            .into_iter()
+            .find(|sample| sample.label() == label)
+            .unwrap();
+        let header = HeaderParams::new(TimeDiff::from_str("1h").unwrap(), vec![], "casper-test");
+        let deploy = make_deploy_sample(
+            session,
+            system_payment::valid(),
+            header,
+            &random_keys(1),
+            &ChainspecLimits::default(),
+        );
+        let elements = parser::parse_deploy(deploy.sample().clone(), &ParserConfig::default())
+            .into_iter()
+            .filter(|element| !element.is_expert())
+            .collect();
+        (deploy.valid(), elements)
+    }
+
+    fn value<'a>(elements: &'a [Element], name: &str) -> &'a str {
+        elements
+            .iter()
+            .find(|element| element.name() == name)
+            .map(|element| element.value())
+            .unwrap_or_else(|| panic!("missing {} element", name))
+    }
+
+    #[test]
+    fn confusing_names_are_displayed_escaped() {
+        // Characters the device can't display are escaped, the rest is displayed verbatim.
+        let escaped = [
+            ("homoglyph", "D\\u{435}legate"),
+            ("whole_script_homoglyph", "\\u{440}\\u{430}\\u{443}"),
+            ("right_to_left_override", "\\u{202e}refsnart nekoT"),
+            ("zero_width_space", "Token\\u{200b}transfer"),
+            ("newline", "Delegate\\nToken transfer"),
+            ("emoji", "Token transfer \\u{1f680}"),
+            ("combining_mark", "De\\u{301}legate"),
+            ("fake_escape", "Token\\\\u{200b}transfer"),
+        ];
+        for (label, name) in confusing_names() {
+            let (valid, elements) = regular_elements(&format!("adversarial_name_{}", label));
+            assert!(valid, "{}", label);
+            assert_eq!(value(&elements, "Type"), "Contract execution");
+            let expected_name = escaped
+                .iter()
+                .find(|(escaped_label, _)| *escaped_label == label)
+                .map_or(name.as_str(), |(_, escaped_name)| *escaped_name);
+            assert_eq!(value(&elements, "Name"), expected_name);
+            assert_eq!(value(&elements, "Amount"), "1 000 000 000 motes");
+        }
+    }
+
+    #[test]
+    fn confusables_are_preceded_by_warning() {
+        let warnings = [
+            ("adversarial_name_homoglyph", "name mixes Latin, Cyrillic"),
+            (
+                "adversarial_name_whole_script_homoglyph",
+                "name is Cyrillic, looks like pay",
+            ),
+            (
+                "adversarial_name_right_to_left_override",
+                "name has invisible U+202E",
+            ),
+            (
+                "adversarial_name_zero_width_space",
+                "name has invisible U+200B",
+            ),
+            (
+                "adversarial_amount_string_homoglyph",
+                "amount mixes Latin, Cyrillic",
+            ),
+            (
+                "adversarial_delegate_name_homoglyph",
+                "name mixes Latin, Cyrillic",
+            ),
+        ];
+        for (label, warning) in warnings {
+            let (valid, elements) = regular_elements(label);
+            assert!(valid, "{}", label);
+            assert_eq!(value(&elements, "Warning"), warning);
+        }
+        let (_, elements) = regular_elements("adversarial_delegate_name_homoglyph");
+        assert_eq!(value(&elements, "Type"), "Delegate");
+        assert!(elements.iter().all(|element| element.name() != "Name"));
+    }
+
+    #[test]
+    fn strings_pose_as_typed_values() {
+        let (valid, elements) = regular_elements("adversarial_amount_string_motes");
+        assert!(valid);
+        assert_eq!(value(&elements, "Amount"), "1 000 000 000 motes");
+
+        let (valid, elements) = regular_elements("adversarial_transfer_recipient_mismatch");
+        assert!(valid);
+        assert_eq!(value(&elements, "Type"), "Token transfer");
+        assert_ne!(value(&elements, "Recipient"), value(&elements, "Target"));
+
+        // Rendered just like the `PublicKey` and `Key::Account` targets, except for the checksum.
+        let key = impersonated_key();
+        let string_targets = [
+            ("public_key", parse_public_key(&key)),
+            ("account_hash", hex::encode(key.to_account_hash())),
+        ];
+        for (label, expected_target) in string_targets {
+            let (valid, elements) =
+                regular_elements(&format!("adversarial_transfer_target_string_{}", label));
+            assert!(!valid, "{}", label);
+            assert_eq!(value(&elements, "Target"), expected_target);
+        }
+    }
+}
@@ -8,7 +8,7 @@ pub(crate) fn valid(entrypoint: &str, ra: Vec<RuntimeArgs>) -> Vec<Sample<Execut
     let mut output = vec![];
 
     for args in ra {
-        for sample in sample_executables(entrypoint, args.clone(), None, true) {
+        for sample in sample_executables(entrypoint, args.clone(), None) {
             output.push(prepend_label(sample, entrypoint));
         }
 
@@ -56,28 +56,27 @@ pub(crate) fn invalid_delegation(entry_point: &str) -> Vec<Sample<ExecutableDepl
             "amount" => 100000u32
         };
 
-        // We're setting the "validity bit" to `true`, otherwise such transaction would
-        // be rejected by the Ledger Hardware and we don't want that. dApps could be written
-        // in such a way that they use similar arguments.
+        // Such transactions are not recognized as auction actions, so the validation rules
+        // compute them as valid generic deploys - otherwise they would be rejected by the Ledger Hardware
+        // and we don't want that. dApps could be written in such a way that they use similar arguments.
         vec![
-            Sample::new("missing_amount", missing_required_amount, true),
-            Sample::new("missing_delegator", missing_required_delegator, true),
-            Sample::new("missing_validator", missing_required_validator, true),
-            Sample::new("invalid_type_amount", invalid_amount_type, true),
+            ("missing_amount", missing_required_amount),
+            ("missing_delegator", missing_required_delegator),
+            ("missing_validator", missing_required_validator),
+            ("invalid_type_amount", invalid_amount_type),
         ]
     };
 
     invalid_args
         .into_iter()
-        .flat_map(|sample_ra| {
-            let (label, ra, valid) = sample_ra.destructure();
-            sample_executables(entry_point, ra, Some(label), valid)
-        })
+        .flat_map(|(label, ra)| sample_executables(entry_point, ra, Some(label.to_string())))
+        // Even though entrypoint is invalid, it's possible that generic transaction (non-native auction)
+        // uses similar set of arguments but changes the entrypoint. In that case, transaction MUSTN'T be invalid
+        // b/c it will get rejected by the Ledger.
         .chain(sample_executables(
             "invalid",
             valid_args.clone(),
             Some("invalid_entrypoint".to_string()),
-            true, // Even though entrypoint is invalid, it's possible that generic transaction (non-native auction) uses similar set of arguments but changes the entrypoint. In that case, transaction MUSTN'T be invalid b/c it will get rejected by the Ledger.
         ))
         .map(|sample| prepend_label(sample, entry_point))
         .collect()
@@ -99,36 +99,27 @@ fn invalid_redelegation() -> Vec<Sample<ExecutableDeployItem>> {
             "new_validator" => new_validator,
         };
 
-        // We're setting the "validity bit" to `true`, otherwise such transaction would
-        // be rejected by the Ledger Hardware and we don't want that. dApps could be written
-        // in such a way that they use similar arguments.
+        // Valid generic deploys, see `commons::invalid_delegation` - except for the one
+        // missing the new validator, which the validation rules reject.
         vec![
-            Sample::new("missing_amount", missing_required_amount, true),
-            Sample::new("missing_delegator", missing_required_delegator, true),
-            Sample::new("missing_validator", missing_required_validator, true),
-            Sample::new(
-                "missing_new_validator",
-                missing_required_new_validator,
-                false,
-            ),
-            Sample::new("invalid_type_amount", invalid_amount_type, true),
+            ("missing_amount", missing_required_amount),
+            ("missing_delegator", missing_required_delegator),
+            ("missing_validator", missing_required_validator),
+            ("missing_new_validator", missing_required_new_validator),
+            ("invalid_type_amount", invalid_amount_type),
         ]
     };
 
     invalid_args_samples
         .into_iter()
-        .flat_map(|sample_ra| {
-            let (label, ra, valid) = sample_ra.destructure();
-            sample_executables(ENTRY_POINT_NAME, ra, Some(label), valid)
-        })
+        .flat_map(|(label, ra)| sample_executables(ENTRY_POINT_NAME, ra, Some(label.to_string())))
         .chain(
             // Transaction with valid args but invalid entrypoint won't be recognized
-            // as proper auction deploy.
+            // as proper auction deploy, see `commons::invalid_delegation`.
             sample_executables(
                 "invalid",
                 valid_args.clone(),
                 Some("invalid_entrypoint".to_string()),
-                true, // Even though entrypoint is invalid, it's possible that generic transaction (non-native auction) uses similar set of arguments but changes the entrypoint. In that case, transaction MUSTN'T be invalid b/c it will get rejected by the Ledger.
             ),
         )
         .map(|sample_invalid_executable| prepend_label(sample_invalid_executable, ENTRY_POINT_NAME))
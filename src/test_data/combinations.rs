@@ -0,0 +1,192 @@
+//! Strategies of combining values of sample dimensions (e.g. amount × id × target × source
+//! of a native transfer) and the coverage they achieve.
+
+use std::{collections::BTreeSet, fmt::Display};
+
+use itertools::Itertools;
+
+/// Environment variable selecting the combination strategy:
+/// `exhaustive` (default), `pairwise` or `<n>-wise`, e.g. `3-wise`.
+pub(crate) const COMBINATION_ENV: &str = "CL_COMBINATION";
+
+/// How values of sample dimensions are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Combination {
+    /// Every combination - the cartesian product of all dimensions.
+    Exhaustive,
+    /// Covering array - every combination of values of any `n` dimensions appears at least once.
+    NWise(usize),
+}
+
+impl Combination {
+    /// Reads the strategy from `CL_COMBINATION`, defaults to `Exhaustive`.
+    pub(crate) fn from_env() -> Self {
+        match std::env::var(COMBINATION_ENV) {
+            Ok(value) => Self::parse(&value)
+                .unwrap_or_else(|| panic!("invalid {}: {}", COMBINATION_ENV, value)),
+            Err(_) => Combination::Exhaustive,
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "exhaustive" => Some(Combination::Exhaustive),
+            "pairwise" => Some(Combination::NWise(2)),
+            _ => value
+                .strip_suffix("-wise")
+                .and_then(|n| n.parse().ok())
+                .filter(|n| *n > 0)
+                .map(Combination::NWise),
+        }
+    }
+
+    /// Strength of the tuples the coverage is reported for - pairs, unless it's a higher n-wise.
+    fn strength(&self) -> usize {
+        match self {
+            Combination::Exhaustive => 2,
+            Combination::NWise(n) => *n,
+        }
+    }
+
+    /// Returns rows of value indices, one index per dimension of given `sizes`.
+    ///
+    /// Exhaustive rows follow the order of nested loops - the last dimension changes the fastest.
+    pub(crate) fn combine(&self, sizes: &[usize]) -> Vec<Vec<usize>> {
+        let all_rows = cartesian_product(sizes);
+        match self {
+            Combination::NWise(n) if *n < sizes.len() => covering_array(sizes, all_rows, *n),
+            _ => all_rows,
+        }
+    }
+}
+
+fn cartesian_product(sizes: &[usize]) -> Vec<Vec<usize>> {
+    sizes
+        .iter()
+        .map(|size| 0..*size)
+        .multi_cartesian_product()
+        .collect()
+}
+
+/// All `(dimension, value)` tuples of `strength` dimensions in `row`.
+fn tuples(row: &[usize], strength: usize) -> Vec<Vec<(usize, usize)>> {
+    row.iter()
+        .cloned()
+        .enumerate()
+        .combinations(strength)
+        .collect()
+}
+
+/// Greedily picks rows covering the most of the not-yet-covered tuples, until all of them are covered.
+/// Deterministic - ties are resolved in favor of the earlier row.
+fn covering_array(sizes: &[usize], all_rows: Vec<Vec<usize>>, strength: usize) -> Vec<Vec<usize>> {
+    let mut uncovered: BTreeSet<Vec<(usize, usize)>> = all_tuples(sizes, strength);
+    let mut rows = vec![];
+    while !uncovered.is_empty() {
+        let best = all_rows
+            .iter()
+            .max_by_key(|row| {
+                let newly_covered = tuples(row, strength)
+                    .iter()
+                    .filter(|tuple| uncovered.contains(*tuple))
+                    .count();
+                // `max_by_key` returns the last maximum, prefer the earlier rows instead.
+                (newly_covered, std::cmp::Reverse(row.to_vec()))
+            })
+            .expect("there are uncovered tuples, so there are rows")
+            .clone();
+        for tuple in tuples(&best, strength) {
+            uncovered.remove(&tuple);
+        }
+        rows.push(best);
+    }
+    rows
+}
+
+fn all_tuples(sizes: &[usize], strength: usize) -> BTreeSet<Vec<(usize, usize)>> {
+    cartesian_product(sizes)
+        .iter()
+        .flat_map(|row| tuples(row, strength))
+        .collect()
+}
+
+/// Coverage achieved by the combined rows of a sample family.
+pub(crate) struct Coverage {
+    family: String,
+    combination: Combination,
+    rows: usize,
+    exhaustive_rows: usize,
+    covered_tuples: usize,
+    all_tuples: usize,
+}
+
+impl Coverage {
+    pub(crate) fn new(
+        family: &str,
+        combination: Combination,
+        sizes: &[usize],
+        rows: &[Vec<usize>],
+    ) -> Self {
+        let strength = combination.strength().min(sizes.len());
+        let covered: BTreeSet<Vec<(usize, usize)>> =
+            rows.iter().flat_map(|row| tuples(row, strength)).collect();
+        Coverage {
+            family: family.to_string(),
+            combination,
+            rows: rows.len(),
+            exhaustive_rows: sizes.iter().product(),
+            covered_tuples: covered.len(),
+            all_tuples: all_tuples(sizes, strength).len(),
+        }
+    }
+}
+
+impl Display for Coverage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} of {} combinations ({:?}), {}-wise coverage {}/{}",
+            self.family,
+            self.rows,
+            self.exhaustive_rows,
+            self.combination,
+            self.combination.strength(),
+            self.covered_tuples,
+            self.all_tuples
+        )
+    }
+}
+
+/// Prints the coverage of every sample family to stderr, so that it doesn't mix with the test vectors.
+pub(crate) fn report(coverage: &[Coverage]) {
+    for family in coverage {
+        eprintln!("Coverage of {}", family);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Combination, Coverage};
+
+    #[test]
+    fn pairwise_covers_all_pairs_with_fewer_rows() {
+        // Dimensions of the valid native transfers: amount × id × target × source.
+        let sizes = [3, 2, 5, 7];
+        let combination = Combination::NWise(2);
+        let rows = combination.combine(&sizes);
+        let coverage = Coverage::new("native_transfer", combination, &sizes, &rows);
+
+        assert_eq!(coverage.covered_tuples, coverage.all_tuples);
+        // Every target has to meet every source at least once.
+        assert!(rows.len() >= 5 * 7);
+        assert!(rows.len() < 3 * 2 * 5 * 7);
+    }
+
+    #[test]
+    fn exhaustive_follows_nested_loops() {
+        let rows = Combination::Exhaustive.combine(&[2, 2]);
+        assert_eq!(rows, vec![vec![0, 0], vec![0, 1], vec![1, 0], vec![1, 1]]);
+        assert_eq!(Combination::parse("3-wise"), Some(Combination::NWise(3)));
+        assert_eq!(Combination::parse("0-wise"), None);
+    }
+}
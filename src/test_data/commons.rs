@@ -1,43 +1,70 @@
 use casper_execution_engine::core::engine_state::ExecutableDeployItem;
-use casper_types::bytesrepr::Bytes;
+use casper_types::bytesrepr::{Bytes, ToBytes};
 use casper_types::{
-    ContractHash, ContractPackageHash, ContractVersion, RuntimeArgs, UREF_ADDR_LENGTH,
+    ContractHash, ContractPackageHash, ContractVersion, NamedArg, RuntimeArgs, UREF_ADDR_LENGTH,
 };
 
-use crate::sample::Sample;
+use crate::{
+    ledger::TxnPhase,
+    parser::validation::{validate_payment, validate_placement, validate_session},
+    sample::Sample,
+};
+
+/// Header of an empty wasm module - the code doesn't have to be executable for the Ledger.
+pub(crate) const WASM_MODULE: [u8; 8] = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+
+/// Returns a session sample, with validity computed from the type rules of the well-known arguments
+/// and whether the item can be executed as a session.
+pub(crate) fn session_sample<S: Into<String>>(
+    label: S,
+    item: ExecutableDeployItem,
+) -> Sample<ExecutableDeployItem> {
+    let valid =
+        validate_session(&item).is_ok() && validate_placement(TxnPhase::Session, &item).is_ok();
+    Sample::new(label, item, valid)
+}
+
+/// Returns a payment sample, with validity computed from the type rules of the well-known arguments
+/// and whether the item can be executed as a payment.
+pub(crate) fn payment_sample<S: Into<String>>(
+    label: S,
+    item: ExecutableDeployItem,
+) -> Sample<ExecutableDeployItem> {
+    let valid =
+        validate_payment(&item).is_ok() && validate_placement(TxnPhase::Payment, &item).is_ok();
+    Sample::new(label, item, valid)
+}
 
 // Using provided `entry_point` and arguments, returns a vector of samples
-// for each of the existing `ExecutableDeployItem` variant.
+// for each of the stored contract `ExecutableDeployItem` variants,
+// versioned ones both with a specific and the latest version.
 pub(crate) fn sample_executables(
     entry_point: &str,
     ra: RuntimeArgs,
     base_label: Option<String>,
-    valid: bool,
 ) -> Vec<Sample<ExecutableDeployItem>> {
     let contract_hash = ContractHash::new([1u8; 32]);
     let contract_package_hash = ContractPackageHash::new([1u8; 32]);
     let contract_version: ContractVersion = 1;
     let contract_name = format!("{}_contract", entry_point);
     let deploy_items = vec![
-        Sample::new(
+        session_sample(
             "type_by_hash",
             ExecutableDeployItem::StoredContractByHash {
                 hash: contract_hash,
                 entry_point: entry_point.to_string(),
                 args: ra.clone(),
             },
-            valid,
         ),
-        Sample::new(
+        session_sample(
             "type_by_name",
             ExecutableDeployItem::StoredContractByName {
                 name: contract_name.to_string(),
                 entry_point: entry_point.to_string(),
                 args: ra.clone(),
             },
-            valid,
         ),
-        Sample::new(
+        session_sample(
             "type_versioned_by_hash",
             ExecutableDeployItem::StoredVersionedContractByHash {
                 hash: contract_package_hash,
@@ -45,17 +72,34 @@ pub(crate) fn sample_executables(
                 entry_point: entry_point.to_string(),
                 args: ra.clone(),
             },
-            valid,
         ),
-        Sample::new(
+        session_sample(
             "type_versioned_by_name",
             ExecutableDeployItem::StoredVersionedContractByName {
-                name: contract_name,
+                name: contract_name.clone(),
                 version: Some(contract_version),
                 entry_point: entry_point.to_string(),
+                args: ra.clone(),
+            },
+        ),
+        // No version - calls whatever the latest version is at the time of execution.
+        session_sample(
+            "type_versioned_by_hash_latest",
+            ExecutableDeployItem::StoredVersionedContractByHash {
+                hash: contract_package_hash,
+                version: None,
+                entry_point: entry_point.to_string(),
+                args: ra.clone(),
+            },
+        ),
+        session_sample(
+            "type_versioned_by_name_latest",
+            ExecutableDeployItem::StoredVersionedContractByName {
+                name: contract_name,
+                version: None,
+                entry_point: entry_point.to_string(),
                 args: ra,
             },
-            valid,
         ),
     ];
 
@@ -72,16 +116,42 @@ pub(crate) fn sample_executables(
 
 // ModuleBytes action calls are too different from other deploy variants to be included in the same generic logic.
 pub(crate) fn sample_module_bytes(ra: RuntimeArgs) -> Sample<ExecutableDeployItem> {
-    Sample::new(
+    session_sample(
         "type_module_bytes",
         ExecutableDeployItem::ModuleBytes {
-            module_bytes: Bytes::new(),
+            module_bytes: Bytes::from(WASM_MODULE.to_vec()),
             args: ra,
         },
-        true,
     )
 }
 
+/// Splits the args, in their order, into sets as large as the chainspec allows -
+/// none of them serializes into more than `max_length` bytes. An arg that alone exceeds the limit is dropped.
+pub(crate) fn split_args(args: Vec<NamedArg>, max_length: u32) -> Vec<RuntimeArgs> {
+    let fits = |args: &[NamedArg]| {
+        RuntimeArgs::from(args.to_vec()).serialized_length() <= max_length as usize
+    };
+    let mut sets = vec![];
+    let mut set: Vec<NamedArg> = vec![];
+    for arg in args {
+        set.push(arg);
+        if fits(&set) {
+            continue;
+        }
+        let arg = set.pop().unwrap();
+        if !set.is_empty() {
+            sets.push(RuntimeArgs::from(std::mem::take(&mut set)));
+        }
+        if fits(&[arg.clone()]) {
+            set.push(arg);
+        }
+    }
+    if !set.is_empty() {
+        sets.push(RuntimeArgs::from(set));
+    }
+    sets
+}
+
 // Prepends `entrypoint` to the current label of `sample`.
 pub(crate) fn prepend_label(
     sample: Sample<ExecutableDeployItem>,
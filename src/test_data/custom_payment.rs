@@ -0,0 +1,77 @@
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_types::{
+    bytesrepr::Bytes, runtime_args, ContractHash, ContractPackageHash, RuntimeArgs, U512,
+};
+
+use crate::sample::Sample;
+
+use super::{
+    commons::{payment_sample, WASM_MODULE},
+    system_payment::PAYMENT_AMOUNT,
+};
+
+const ENTRYPOINT: &str = "pay";
+const CONTRACT_NAME: &str = "payment_contract";
+
+/// Returns payments that aren't the system payment: custom wasm and calls to each of the stored
+/// contract variants, with and without `amount`.
+///
+/// We can't tell what arguments custom payment logic expects, so all of them are valid payments.
+/// Only a native transfer can be paid without `amount` though - any other session buys its gas with it.
+pub(super) fn samples() -> Vec<Sample<ExecutableDeployItem>> {
+    let with_amount = runtime_args! {
+        "amount" => U512::from(PAYMENT_AMOUNT)
+    };
+    let without_amount = RuntimeArgs::new();
+
+    let mut samples = vec![];
+    for (args_label, args) in [("amount", with_amount), ("no_amount", without_amount)] {
+        let payments = vec![
+            (
+                "custom_wasm",
+                ExecutableDeployItem::ModuleBytes {
+                    module_bytes: Bytes::from(WASM_MODULE.to_vec()),
+                    args: args.clone(),
+                },
+            ),
+            (
+                "stored_by_hash",
+                ExecutableDeployItem::StoredContractByHash {
+                    hash: ContractHash::new([2u8; 32]),
+                    entry_point: ENTRYPOINT.to_string(),
+                    args: args.clone(),
+                },
+            ),
+            (
+                "stored_by_name",
+                ExecutableDeployItem::StoredContractByName {
+                    name: CONTRACT_NAME.to_string(),
+                    entry_point: ENTRYPOINT.to_string(),
+                    args: args.clone(),
+                },
+            ),
+            (
+                "stored_versioned_by_hash",
+                ExecutableDeployItem::StoredVersionedContractByHash {
+                    hash: ContractPackageHash::new([2u8; 32]),
+                    version: Some(1),
+                    entry_point: ENTRYPOINT.to_string(),
+                    args: args.clone(),
+                },
+            ),
+            (
+                "stored_versioned_by_name",
+                ExecutableDeployItem::StoredVersionedContractByName {
+                    name: CONTRACT_NAME.to_string(),
+                    version: Some(1),
+                    entry_point: ENTRYPOINT.to_string(),
+                    args,
+                },
+            ),
+        ];
+        samples.extend(payments.into_iter().map(|(label, payment)| {
+            payment_sample(format!("payment_{}_{}", label, args_label), payment)
+        }));
+    }
+    samples
+}
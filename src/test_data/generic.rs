@@ -3,30 +3,36 @@ use std::fmt::Debug;
 use casper_execution_engine::core::engine_state::ExecutableDeployItem;
 use casper_types::{
     account::{AccountHash, ACCOUNT_HASH_LENGTH},
-    bytesrepr::{Bytes, ToBytes},
-    AccessRights, AsymmetricType, CLTyped, CLValue, DeployHash, EraId, Key, NamedArg, PublicKey,
-    RuntimeArgs, TransferAddr, URef, DEPLOY_HASH_LENGTH, KEY_DICTIONARY_LENGTH, KEY_HASH_LENGTH,
-    TRANSFER_ADDR_LENGTH, U128, U256, U512, UREF_ADDR_LENGTH,
+    bytesrepr::{Bytes, ToBytes, OPTION_NONE_TAG, OPTION_SOME_TAG, RESULT_ERR_TAG, RESULT_OK_TAG},
+    AccessRights, AsymmetricType, CLType, CLTyped, CLValue, DeployHash, EraId, Key, NamedArg,
+    PublicKey, RuntimeArgs, TransferAddr, URef, DEPLOY_HASH_LENGTH, KEY_DICTIONARY_LENGTH,
+    KEY_HASH_LENGTH, TRANSFER_ADDR_LENGTH, U128, U256, U512, UREF_ADDR_LENGTH,
 };
 use rand::{prelude::SliceRandom, Rng};
 
 use crate::{
     sample::Sample,
-    test_data::commons::{sample_executables, sample_module_bytes},
+    test_data::commons::{sample_executables, sample_module_bytes, split_args},
 };
 
 use super::commons::UREF_ADDR;
 
-pub(crate) fn valid<R: Rng>(rng: &mut R) -> Vec<Sample<ExecutableDeployItem>> {
+/// Returns generic contract calls with sample args. Composite args nest other values up to `composite_depth`,
+/// e.g. `List<Option<U512>>` is of depth 2. None of the args sets serializes into more than `max_args_length` bytes.
+pub(crate) fn valid<R: Rng>(
+    rng: &mut R,
+    composite_depth: usize,
+    max_args_length: u32,
+) -> Vec<Sample<ExecutableDeployItem>> {
     const ENTRYPOINT: &str = "generic-txn-entrypoint";
-    let rargs: Vec<RuntimeArgs> = sample_args(rng);
+    let rargs: Vec<RuntimeArgs> = sample_args(rng, composite_depth, max_args_length);
 
     let mut output = Vec::with_capacity(rargs.len());
 
     output.push(sample_module_bytes(rargs.first().cloned().unwrap()));
 
     for args in rargs {
-        for sample in sample_executables(ENTRYPOINT, args.clone(), None, true) {
+        for sample in sample_executables(ENTRYPOINT, args.clone(), None) {
             output.push(sample)
         }
     }
@@ -52,7 +58,11 @@ where
 }
 
 #[allow(unused_parens)]
-fn sample_args<R: Rng>(rng: &mut R) -> Vec<RuntimeArgs> {
+fn sample_args<R: Rng>(
+    rng: &mut R,
+    composite_depth: usize,
+    max_args_length: u32,
+) -> Vec<RuntimeArgs> {
     let mut named_args: Vec<NamedArg> = vec![
         vec_to_clvalues(vec![true, false]),
         vec_to_clvalues(vec![i32::MIN, 0, i32::MAX]),
@@ -115,7 +125,6 @@ fn sample_args<R: Rng>(rng: &mut R) -> Vec<RuntimeArgs> {
         vec![to_clvalue_labeled((11u8,))],
         vec![to_clvalue_labeled((11u8, 1111u64))],
         vec![to_clvalue_labeled((0u8, true, "tuple3"))],
-        // ("map".to_string(), todo!())
     ]
     .into_iter()
     .flatten()
@@ -129,19 +138,107 @@ fn sample_args<R: Rng>(rng: &mut R) -> Vec<RuntimeArgs> {
     for _ in 0..15 {
         named_args.shuffle(rng);
         let n = rng.gen_range(2..named_args_count);
-        out.push(
-            named_args
-                .iter()
-                .take(n as usize)
-                .cloned()
-                .collect::<Vec<NamedArg>>()
-                .into(),
-        );
+        // Only as many of them as fit into the args length limit.
+        let args = named_args.iter().take(n as usize).cloned().collect();
+        out.extend(split_args(args, max_args_length).into_iter().take(1));
     }
 
+    out.extend(composite_args(composite_depth, max_args_length));
     out
 }
 
+/// Returns sets of args for every depth from 1 to `max_depth`. Each depth wraps every value
+/// of the previous depth in every composite type: list, option, result, map and tuple.
+/// Values of a depth are split into as few sets as `max_args_length` allows.
+/// Values of the same type (e.g. `Some` and `None` option) may be in the same set, so args are named
+/// by their position in it: `arg-0`, `arg-1` etc.
+fn composite_args(max_depth: usize, max_args_length: u32) -> Vec<RuntimeArgs> {
+    let named = |values: Vec<CLValue>| -> Vec<NamedArg> {
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(idx, value)| NamedArg::new(format!("arg-{}", idx), value))
+            .collect()
+    };
+    let mut values = composite_leaves();
+    (0..max_depth)
+        .flat_map(|_| {
+            values = values.iter().flat_map(wrap).collect();
+            // Renumbering the args of a set only shortens their names, so the set still fits.
+            split_args(named(values.clone()), max_args_length)
+                .into_iter()
+                .map(|args| {
+                    let values = args.named_args().map(|arg| arg.cl_value().clone());
+                    RuntimeArgs::from(named(values.collect()))
+                })
+                .collect::<Vec<RuntimeArgs>>()
+        })
+        .collect()
+}
+
+fn composite_leaves() -> Vec<CLValue> {
+    vec![
+        CLValue::from_t(u8::MAX).unwrap(),
+        CLValue::from_t(U512::from(1_000_000_000u64)).unwrap(),
+        CLValue::from_t("sample-string".to_string()).unwrap(),
+        CLValue::from_t(Key::Account(AccountHash::new([1u8; ACCOUNT_HASH_LENGTH]))).unwrap(),
+        CLValue::from_t(PublicKey::ed25519_from_bytes([1u8; 32]).unwrap()).unwrap(),
+    ]
+}
+
+// Types can't be nested arbitrarily with `CLValue::from_t`, so composite values
+// are built from the serialized `inner` value, following the bytesrepr of each composite type.
+fn wrap(inner: &CLValue) -> Vec<CLValue> {
+    let inner_type = || Box::new(inner.cl_type().clone());
+    let bytes = inner.inner_bytes().as_slice();
+    let map_key = CLValue::from_t("key".to_string()).unwrap();
+    let tuple_key = CLValue::from_t(Key::Hash([2u8; KEY_HASH_LENGTH])).unwrap();
+    vec![
+        composite(
+            CLType::List(inner_type()),
+            &[2u32.to_bytes().unwrap().as_slice(), bytes, bytes],
+        ),
+        composite(
+            CLType::Option(inner_type()),
+            &[&[OPTION_SOME_TAG][..], bytes],
+        ),
+        composite(CLType::Option(inner_type()), &[&[OPTION_NONE_TAG][..]]),
+        composite(
+            CLType::Result {
+                ok: inner_type(),
+                err: Box::new(CLType::String),
+            },
+            &[&[RESULT_OK_TAG][..], bytes],
+        ),
+        composite(
+            CLType::Result {
+                ok: Box::new(CLType::Unit),
+                err: inner_type(),
+            },
+            &[&[RESULT_ERR_TAG][..], bytes],
+        ),
+        composite(
+            CLType::Map {
+                key: Box::new(CLType::String),
+                value: inner_type(),
+            },
+            &[
+                1u32.to_bytes().unwrap().as_slice(),
+                map_key.inner_bytes().as_slice(),
+                bytes,
+            ],
+        ),
+        composite(
+            CLType::Tuple2([inner_type(), Box::new(CLType::Key)]),
+            &[bytes, tuple_key.inner_bytes().as_slice()],
+        ),
+    ]
+}
+
+fn composite(cl_type: CLType, parts: &[&[u8]]) -> CLValue {
+    CLValue::from_components(cl_type, parts.concat())
+}
+
 fn sample_urefs() -> Vec<URef> {
     vec![
         URef::new(UREF_ADDR, AccessRights::NONE),
@@ -189,3 +286,76 @@ fn sample_keys() -> Vec<Key> {
         checksum_registry_key,
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use casper_types::{bytesrepr::ToBytes, CLType, CLValue, Key, RuntimeArgs, U512};
+
+    use crate::utils::cl_value_to_string;
+
+    use super::{composite_args, composite_leaves, wrap};
+
+    /// First arg of the type.
+    fn arg_of_type<'a>(args: &'a RuntimeArgs, cl_type: &CLType) -> &'a CLValue {
+        args.named_args()
+            .map(|arg| arg.cl_value())
+            .find(|value| value.cl_type() == cl_type)
+            .unwrap_or_else(|| panic!("missing {:?} arg", cl_type))
+    }
+
+    #[test]
+    fn composite_args_match_typed_values() {
+        // Without a limit, there's a single set of every depth.
+        let args = composite_args(3, u32::MAX);
+        assert_eq!(args.len(), 3);
+
+        let list_of_lists = arg_of_type(
+            &args[1],
+            &CLType::List(Box::new(CLType::List(Box::new(CLType::U8)))),
+        );
+        assert_eq!(
+            list_of_lists,
+            &CLValue::from_t(vec![vec![u8::MAX; 2]; 2]).unwrap()
+        );
+        let optional_key = arg_of_type(&args[0], &CLType::Option(Box::new(CLType::Key)));
+        assert!(optional_key.clone().into_t::<Option<Key>>().is_ok());
+        let result = arg_of_type(
+            &args[0],
+            &CLType::Result {
+                ok: Box::new(CLType::U512),
+                err: Box::new(CLType::String),
+            },
+        );
+        assert_eq!(
+            result.clone().into_t::<Result<U512, String>>().unwrap(),
+            Ok(U512::from(1_000_000_000u64))
+        );
+
+        // None of them can make the parser panic.
+        for value in args.iter().flat_map(|args| args.named_args()) {
+            cl_value_to_string(value.cl_value());
+        }
+    }
+
+    #[test]
+    fn composite_arg_names_are_unique() {
+        for args in composite_args(2, 1024) {
+            let names: BTreeSet<&str> = args.named_args().map(|arg| arg.name()).collect();
+            assert_eq!(names.len(), args.len());
+        }
+    }
+
+    #[test]
+    fn composite_args_fit_into_the_limit() {
+        let args = composite_args(2, 1024);
+        assert!(args.iter().all(|args| args.serialized_length() <= 1024));
+
+        // None of the values is dropped on the way.
+        let depth_1: Vec<CLValue> = composite_leaves().iter().flat_map(wrap).collect();
+        let depth_2 = depth_1.iter().flat_map(wrap).count();
+        let count: usize = args.iter().map(|args| args.len()).sum();
+        assert_eq!(count, depth_1.len() + depth_2);
+    }
+}
@@ -3,38 +3,57 @@ use casper_types::{runtime_args, AccessRights, RuntimeArgs, URef, U512};
 
 use crate::{sample::Sample, test_data::TransferTarget};
 
-use super::{commons::UREF_ADDR, NativeTransfer, TransferSource};
+use super::{
+    combinations::{Combination, Coverage},
+    commons::{session_sample, UREF_ADDR},
+    NativeTransfer, TransferSource, TRANSFER_AMOUNT,
+};
 
-/// Given collection of native target inputs,
-/// for every combination of them creates a `NativeTransfer` sample.
+/// Given collection of native target inputs, creates a native transfer sample
+/// for every combination of them picked by the `combination` strategy.
 fn native_transfer_samples(
     amounts: &[U512],
     transfer_id: &[u64],
     targets: &[TransferTarget],
     sources: &[TransferSource],
-) -> Vec<Sample<NativeTransfer>> {
-    let mut samples: Vec<Sample<NativeTransfer>> = vec![];
-
-    for amount in amounts {
-        for id in transfer_id {
-            for target in targets {
-                for source in sources {
-                    let label = format!("native_transfer_{}_{}", target.label(), source.label());
-                    let nt = NativeTransfer::new(target.clone(), *amount, *id, source.clone());
-                    let sample = Sample::new(label, nt, true);
-                    samples.push(sample);
-                }
-            }
-        }
-    }
-
-    samples
+    combination: Combination,
+) -> (Vec<Sample<ExecutableDeployItem>>, Coverage) {
+    let sizes = [
+        amounts.len(),
+        transfer_id.len(),
+        targets.len(),
+        sources.len(),
+    ];
+    let rows = combination.combine(&sizes);
+    let coverage = Coverage::new("native_transfer", combination, &sizes, &rows);
+
+    let samples = rows
+        .iter()
+        .map(|row| {
+            let (amount, id, target, source) = (
+                amounts[row[0]],
+                transfer_id[row[1]],
+                &targets[row[2]],
+                &sources[row[3]],
+            );
+            let label = format!("native_transfer_{}_{}", target.label(), source.label());
+            let nt = NativeTransfer::new(target.clone(), amount, id, source.clone());
+            transfer_sample(label, nt)
+        })
+        .collect();
+
+    (samples, coverage)
+}
+
+fn transfer_sample(label: String, nt: NativeTransfer) -> Sample<ExecutableDeployItem> {
+    session_sample(label, ExecutableDeployItem::Transfer { args: nt.into() })
 }
 
-/// Returns valid native transfer samples.
-pub(super) fn valid() -> Vec<Sample<ExecutableDeployItem>> {
-    let amount_min = U512::from(0u8);
-    let amount_mid = U512::from(100000000);
+/// Returns valid native transfer samples and the coverage of their combinations.
+pub(super) fn valid(combination: Combination) -> (Vec<Sample<ExecutableDeployItem>>, Coverage) {
+    // Transfers of less than the minimum are invalid, see `amount_samples` for the smaller amounts.
+    let amount_min = U512::from(TRANSFER_AMOUNT);
+    let amount_mid = U512::from(100_000_000_000u64);
     let amount_max = U512::MAX;
     let amounts = vec![amount_min, amount_mid, amount_max];
     let id_min = u64::MIN;
@@ -63,21 +82,60 @@ pub(super) fn valid() -> Vec<Sample<ExecutableDeployItem>> {
         .chain(vec![TransferSource::none()])
         .collect();
 
-    native_transfer_samples(&amounts, &transfer_id, &targets, &sources)
-        .into_iter()
-        .map(|s| {
-            let (label, sample, validity) = s.destructure();
-            Sample::new(
-                label,
-                ExecutableDeployItem::Transfer {
-                    args: sample.into(),
-                },
-                validity,
-            )
-        })
+    native_transfer_samples(&amounts, &transfer_id, &targets, &sources, combination)
+}
+
+/// Returns native transfers between purses where access rights vary
+/// for the source purse (with a fixed target) and for the target purse (from the main purse).
+pub(super) fn access_rights() -> Vec<Sample<ExecutableDeployItem>> {
+    let amount = U512::from(TRANSFER_AMOUNT);
+    let id = 1u64;
+    let all_access_rights = vec![
+        AccessRights::NONE,
+        AccessRights::READ,
+        AccessRights::WRITE,
+        AccessRights::ADD,
+        AccessRights::READ_ADD,
+        AccessRights::READ_WRITE,
+        AccessRights::ADD_WRITE,
+        AccessRights::READ_ADD_WRITE,
+    ];
+
+    let varying_source = all_access_rights.iter().map(|ar| {
+        let label = format!(
+            "native_transfer_target_uref_source_uref_{}",
+            access_rights_label(*ar)
+        );
+        let source = TransferSource::uref(URef::new(UREF_ADDR, *ar));
+        (
+            label,
+            NativeTransfer::new(TransferTarget::uref(), amount, id, source),
+        )
+    });
+
+    let varying_target = all_access_rights.iter().map(|ar| {
+        let label = format!(
+            "native_transfer_target_uref_{}_source_none",
+            access_rights_label(*ar)
+        );
+        let target = TransferTarget::uref_with_access_rights(*ar);
+        (
+            label,
+            NativeTransfer::new(target, amount, id, TransferSource::none()),
+        )
+    });
+
+    varying_source
+        .chain(varying_target)
+        .map(|(label, nt)| transfer_sample(label, nt))
         .collect()
 }
 
+// Access rights as the octal suffix of the formatted `URef`, e.g. `007` for read, add and write.
+fn access_rights_label(access_rights: AccessRights) -> String {
+    format!("{:03o}", access_rights.bits())
+}
+
 /// Returns invalid native transfer samples.
 pub(super) fn invalid() -> Vec<Sample<ExecutableDeployItem>> {
     let missing_required_amount: RuntimeArgs = runtime_args! {
@@ -98,20 +156,19 @@ pub(super) fn invalid() -> Vec<Sample<ExecutableDeployItem>> {
         "id" => 1u64,
     };
 
-    let invalid_transfer_args: Vec<Sample<RuntimeArgs>> = vec![
-        Sample::new("missing_amount", missing_required_amount, false),
-        Sample::new("missing_id", missing_required_id, false),
-        Sample::new("missing_target", missing_required_target, false),
-        Sample::new("invalid_type_amount", invalid_amount_type, false),
+    let invalid_transfer_args: Vec<(&str, RuntimeArgs)> = vec![
+        ("missing_amount", missing_required_amount),
+        ("missing_id", missing_required_id),
+        ("missing_target", missing_required_target),
+        ("invalid_type_amount", invalid_amount_type),
     ];
 
     invalid_transfer_args
         .into_iter()
-        .map(|sample_ra| {
-            let (label, ra, validity) = sample_ra.destructure(); // TODO
+        .map(|(label, ra)| {
             let sample_invalid_transfer = ExecutableDeployItem::Transfer { args: ra };
             let new_label = format!("native_transfer_{}", label);
-            Sample::new(new_label, sample_invalid_transfer, validity)
+            session_sample(new_label, sample_invalid_transfer)
         })
         .collect()
 }
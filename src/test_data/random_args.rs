@@ -0,0 +1,749 @@
+//! Deterministic generator of random, well-typed `CLValue`s of every `CLType`, for fuzz-style
+//! generic samples, and the shrinker reducing a value that can't be rendered to a minimal reproducer.
+
+use std::{
+    cmp::Ordering,
+    panic::{self, AssertUnwindSafe},
+};
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{
+        FromBytes, ToBytes, OPTION_NONE_TAG, OPTION_SOME_TAG, RESULT_ERR_TAG, RESULT_OK_TAG,
+    },
+    AccessRights, CLType, CLTyped, CLValue, DeployHash, EraId, Key, NamedArg, PublicKey,
+    RuntimeArgs, SecretKey, TimeDiff, TransferAddr, URef, U128, U256, U512,
+};
+use rand::{distributions::Alphanumeric, seq::SliceRandom, Rng};
+
+use crate::{chainspec::ChainspecLimits, ledger, parser::config::ParserConfig};
+
+use super::{
+    commons::{session_sample, split_args},
+    make_deploy_sample, random_keys, system_payment, HeaderParams,
+};
+
+/// Maximum nesting depth of the random types, e.g. `List<Option<U512>>` is of depth 2.
+const MAX_DEPTH: usize = 3;
+/// Number of random args sets.
+const ARGS_SETS_COUNT: usize = 5;
+/// Number of random args in each set.
+const ARGS_COUNT: usize = 8;
+/// Lists and maps have at most this many items.
+const MAX_ITEMS: usize = 3;
+/// Lengths of random strings - the longest ones span multiple pages.
+const STRING_LENGTHS: [usize; 5] = [0, 1, 17, 100, 300];
+/// Sizes of random byte arrays, including odd ones around the common 32 bytes.
+const BYTE_ARRAY_SIZES: [u32; 7] = [0, 1, 7, 31, 32, 33, 65];
+
+/// Number of `CLType` variants that don't nest other types (`ByteArray` of any size counts once).
+const SIMPLE_TYPES_COUNT: u8 = 16;
+/// Number of `CLType` variants that nest other types.
+const COMPOSITE_TYPES_COUNT: u8 = 7;
+
+/// Random value, kept as a tree so that it can be shrunk part by part.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum RandomValue {
+    /// Value of a type that doesn't nest other types.
+    Leaf(CLValue),
+    Option {
+        inner: CLType,
+        value: Option<Box<RandomValue>>,
+    },
+    List {
+        item: CLType,
+        items: Vec<RandomValue>,
+    },
+    Result {
+        ok: CLType,
+        err: CLType,
+        value: Result<Box<RandomValue>, Box<RandomValue>>,
+    },
+    Map {
+        key: CLType,
+        value: CLType,
+        entries: Vec<(RandomValue, RandomValue)>,
+    },
+    /// Tuple of 1 to 3 values.
+    Tuple(Vec<RandomValue>),
+}
+
+impl RandomValue {
+    pub(crate) fn cl_type(&self) -> CLType {
+        match self {
+            RandomValue::Leaf(value) => value.cl_type().clone(),
+            RandomValue::Option { inner, .. } => CLType::Option(Box::new(inner.clone())),
+            RandomValue::List { item, .. } => CLType::List(Box::new(item.clone())),
+            RandomValue::Result { ok, err, .. } => CLType::Result {
+                ok: Box::new(ok.clone()),
+                err: Box::new(err.clone()),
+            },
+            RandomValue::Map { key, value, .. } => CLType::Map {
+                key: Box::new(key.clone()),
+                value: Box::new(value.clone()),
+            },
+            RandomValue::Tuple(items) => {
+                let mut types = items.iter().map(|item| Box::new(item.cl_type()));
+                let mut next = || types.next().expect("tuple item");
+                match items.len() {
+                    1 => CLType::Tuple1([next()]),
+                    2 => CLType::Tuple2([next(), next()]),
+                    _ => CLType::Tuple3([next(), next(), next()]),
+                }
+            }
+        }
+    }
+
+    /// Compares two values of the same type the way the Rust types they deserialize to are ordered.
+    fn cmp_as_typed(&self, other: &RandomValue) -> Ordering {
+        match (self, other) {
+            (RandomValue::Leaf(a), RandomValue::Leaf(b)) => cmp_leaves(a, b),
+            (RandomValue::Option { value: a, .. }, RandomValue::Option { value: b, .. }) => {
+                match (a, b) {
+                    (Some(a), Some(b)) => a.cmp_as_typed(b),
+                    _ => a.is_some().cmp(&b.is_some()),
+                }
+            }
+            (RandomValue::List { items: a, .. }, RandomValue::List { items: b, .. })
+            | (RandomValue::Tuple(a), RandomValue::Tuple(b)) => cmp_sequences(a.iter(), b.iter()),
+            (RandomValue::Result { value: a, .. }, RandomValue::Result { value: b, .. }) => {
+                match (a, b) {
+                    (Ok(a), Ok(b)) | (Err(a), Err(b)) => a.cmp_as_typed(b),
+                    // `Ok` is declared, and so ordered, before `Err`.
+                    _ => b.is_ok().cmp(&a.is_ok()),
+                }
+            }
+            (RandomValue::Map { entries: a, .. }, RandomValue::Map { entries: b, .. }) => {
+                let flatten = |entries: &'_ [(RandomValue, RandomValue)]| {
+                    entries
+                        .iter()
+                        .flat_map(|(key, value)| vec![key.clone(), value.clone()])
+                        .collect::<Vec<_>>()
+                };
+                cmp_sequences(flatten(a).iter(), flatten(b).iter())
+            }
+            _ => panic!("compared values should be of the same type"),
+        }
+    }
+
+    /// Serializes the value following the bytesrepr of its type.
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        match self {
+            RandomValue::Leaf(value) => bytes.extend_from_slice(value.inner_bytes()),
+            RandomValue::Option { value: None, .. } => bytes.push(OPTION_NONE_TAG),
+            RandomValue::Option {
+                value: Some(value), ..
+            } => {
+                bytes.push(OPTION_SOME_TAG);
+                value.write_bytes(bytes);
+            }
+            RandomValue::List { items, .. } => {
+                bytes.extend(write_len(items.len()));
+                items.iter().for_each(|item| item.write_bytes(bytes));
+            }
+            RandomValue::Result { value: Ok(ok), .. } => {
+                bytes.push(RESULT_OK_TAG);
+                ok.write_bytes(bytes);
+            }
+            RandomValue::Result {
+                value: Err(err), ..
+            } => {
+                bytes.push(RESULT_ERR_TAG);
+                err.write_bytes(bytes);
+            }
+            RandomValue::Map { entries, .. } => {
+                bytes.extend(write_len(entries.len()));
+                for (key, value) in entries {
+                    key.write_bytes(bytes);
+                    value.write_bytes(bytes);
+                }
+            }
+            RandomValue::Tuple(items) => items.iter().for_each(|item| item.write_bytes(bytes)),
+        }
+    }
+
+    pub(crate) fn to_cl_value(&self) -> CLValue {
+        let mut bytes = vec![];
+        self.write_bytes(&mut bytes);
+        CLValue::from_components(self.cl_type(), bytes)
+    }
+
+    /// Smaller values of the same type - so that they can replace this one inside of a composite value.
+    fn simpler(&self) -> Vec<RandomValue> {
+        match self {
+            RandomValue::Leaf(value) => {
+                let mut simpler = vec![];
+                let minimal = minimal_leaf(value.cl_type());
+                if &minimal != value {
+                    simpler.push(RandomValue::Leaf(minimal));
+                }
+                if let Ok(string) = value.clone().into_t::<String>() {
+                    let half: String = string.chars().take(string.chars().count() / 2).collect();
+                    if !half.is_empty() {
+                        simpler.push(typed(half));
+                    }
+                }
+                simpler
+            }
+            RandomValue::Option { value: None, .. } => vec![],
+            RandomValue::Option {
+                inner,
+                value: Some(value),
+            } => std::iter::once(None)
+                .chain(
+                    value
+                        .simpler()
+                        .into_iter()
+                        .map(|value| Some(Box::new(value))),
+                )
+                .map(|value| RandomValue::Option {
+                    inner: inner.clone(),
+                    value,
+                })
+                .collect(),
+            RandomValue::List { item, items } => simpler_items(items, RandomValue::simpler)
+                .into_iter()
+                .map(|items| RandomValue::List {
+                    item: item.clone(),
+                    items,
+                })
+                .collect(),
+            RandomValue::Result { ok, err, value } => {
+                let simpler: Vec<_> = match value {
+                    Ok(value) => value
+                        .simpler()
+                        .into_iter()
+                        .map(|v| Ok(Box::new(v)))
+                        .collect(),
+                    Err(value) => value
+                        .simpler()
+                        .into_iter()
+                        .map(|v| Err(Box::new(v)))
+                        .collect(),
+                };
+                simpler
+                    .into_iter()
+                    .map(|value| RandomValue::Result {
+                        ok: ok.clone(),
+                        err: err.clone(),
+                        value,
+                    })
+                    .collect()
+            }
+            RandomValue::Map {
+                key,
+                value,
+                entries,
+            } => simpler_items(entries, |(entry_key, entry_value)| {
+                let simpler_keys = entry_key
+                    .simpler()
+                    .into_iter()
+                    .map(|simpler| (simpler, entry_value.clone()));
+                let simpler_values = entry_value
+                    .simpler()
+                    .into_iter()
+                    .map(|simpler| (entry_key.clone(), simpler));
+                simpler_keys.chain(simpler_values).collect()
+            })
+            .into_iter()
+            // Simpler keys may collide with, or go before, the other ones.
+            .filter(|entries| {
+                entries
+                    .windows(2)
+                    .all(|pair| pair[0].0.cmp_as_typed(&pair[1].0) == Ordering::Less)
+            })
+            .map(|entries| RandomValue::Map {
+                key: key.clone(),
+                value: value.clone(),
+                entries,
+            })
+            .collect(),
+            RandomValue::Tuple(items) => (0..items.len())
+                .flat_map(|idx| {
+                    items[idx].simpler().into_iter().map(move |simpler| {
+                        let mut items = items.clone();
+                        items[idx] = simpler;
+                        RandomValue::Tuple(items)
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// Smaller values of any type: the nested values, shorter byte arrays and the simpler values.
+    fn shrink_candidates(&self) -> Vec<RandomValue> {
+        let mut candidates: Vec<RandomValue> = match self {
+            RandomValue::Leaf(value) => match value.cl_type() {
+                CLType::ByteArray(size) if *size > 0 => {
+                    let half = size / 2;
+                    let bytes = value.inner_bytes()[..half as usize].to_vec();
+                    vec![RandomValue::Leaf(CLValue::from_components(
+                        CLType::ByteArray(half),
+                        bytes,
+                    ))]
+                }
+                _ => vec![],
+            },
+            RandomValue::Option { value, .. } => value.iter().map(|v| (**v).clone()).collect(),
+            RandomValue::List { items, .. } | RandomValue::Tuple(items) => items.clone(),
+            RandomValue::Result { value, .. } => match value {
+                Ok(value) | Err(value) => vec![(**value).clone()],
+            },
+            RandomValue::Map { entries, .. } => entries
+                .iter()
+                .flat_map(|(key, value)| vec![key.clone(), value.clone()])
+                .collect(),
+        };
+        candidates.extend(self.simpler());
+        candidates
+    }
+}
+
+/// Removes each of the items, or replaces it with each of its `simpler` versions.
+fn simpler_items<T: Clone, F: Fn(&T) -> Vec<T>>(items: &[T], simpler: F) -> Vec<Vec<T>> {
+    let mut candidates = vec![];
+    for idx in 0..items.len() {
+        let mut without = items.to_vec();
+        without.remove(idx);
+        candidates.push(without);
+        for simpler_item in simpler(&items[idx]) {
+            let mut replaced = items.to_vec();
+            replaced[idx] = simpler_item;
+            candidates.push(replaced);
+        }
+    }
+    candidates
+}
+
+/// Lexicographic comparison, like the one of `Vec`, tuples and `BTreeMap`.
+fn cmp_sequences<'a, I: Iterator<Item = &'a RandomValue>>(a: I, b: I) -> Ordering {
+    let mut b = b;
+    for item in a {
+        match b.next() {
+            Some(other) => match item.cmp_as_typed(other) {
+                Ordering::Equal => {}
+                ordering => return ordering,
+            },
+            None => return Ordering::Greater,
+        }
+    }
+    if b.next().is_some() {
+        Ordering::Less
+    } else {
+        Ordering::Equal
+    }
+}
+
+fn cmp_leaves(a: &CLValue, b: &CLValue) -> Ordering {
+    fn cmp<T: CLTyped + FromBytes + Ord>(a: &CLValue, b: &CLValue) -> Ordering {
+        let typed = |value: &CLValue| value.clone().into_t::<T>().expect("well-typed value");
+        typed(a).cmp(&typed(b))
+    }
+    match a.cl_type() {
+        CLType::Bool => cmp::<bool>(a, b),
+        CLType::I32 => cmp::<i32>(a, b),
+        CLType::I64 => cmp::<i64>(a, b),
+        CLType::U8 => cmp::<u8>(a, b),
+        CLType::U32 => cmp::<u32>(a, b),
+        CLType::U64 => cmp::<u64>(a, b),
+        CLType::U128 => cmp::<U128>(a, b),
+        CLType::U256 => cmp::<U256>(a, b),
+        CLType::U512 => cmp::<U512>(a, b),
+        CLType::Unit => Ordering::Equal,
+        CLType::String => cmp::<String>(a, b),
+        CLType::Key => cmp::<Key>(a, b),
+        CLType::URef => cmp::<URef>(a, b),
+        CLType::PublicKey => cmp::<PublicKey>(a, b),
+        // Byte arrays are ordered by their bytes, `Any` values have no Rust type - ordered the same.
+        _ => a.inner_bytes().cmp(b.inner_bytes()),
+    }
+}
+
+fn write_len(len: usize) -> Vec<u8> {
+    (len as u32).to_bytes().expect("u32 should serialize")
+}
+
+fn typed<T: CLTyped + ToBytes>(value: T) -> RandomValue {
+    RandomValue::Leaf(CLValue::from_t(value).expect("simple values should serialize"))
+}
+
+/// The simplest value of a type that doesn't nest other types.
+fn minimal_leaf(cl_type: &CLType) -> CLValue {
+    let value = match cl_type {
+        CLType::Bool => CLValue::from_t(false),
+        CLType::I32 => CLValue::from_t(0i32),
+        CLType::I64 => CLValue::from_t(0i64),
+        CLType::U8 => CLValue::from_t(0u8),
+        CLType::U32 => CLValue::from_t(0u32),
+        CLType::U64 => CLValue::from_t(0u64),
+        CLType::U128 => CLValue::from_t(U128::zero()),
+        CLType::U256 => CLValue::from_t(U256::zero()),
+        CLType::U512 => CLValue::from_t(U512::zero()),
+        CLType::Unit => CLValue::from_t(()),
+        CLType::String => CLValue::from_t(String::new()),
+        CLType::Key => CLValue::from_t(Key::Hash([0u8; 32])),
+        CLType::URef => CLValue::from_t(URef::new([0u8; 32], AccessRights::NONE)),
+        CLType::PublicKey => CLValue::from_t(PublicKey::System),
+        CLType::ByteArray(size) => Ok(CLValue::from_components(
+            cl_type.clone(),
+            vec![0u8; *size as usize],
+        )),
+        other => Ok(CLValue::from_components(other.clone(), vec![])),
+    };
+    value.expect("simple values should serialize")
+}
+
+/// Returns a random type, nesting other types up to `depth` levels.
+pub(crate) fn random_type<R: Rng>(rng: &mut R, depth: usize) -> CLType {
+    let types_count = if depth == 0 {
+        SIMPLE_TYPES_COUNT
+    } else {
+        SIMPLE_TYPES_COUNT + COMPOSITE_TYPES_COUNT
+    };
+    match rng.gen_range(0..types_count) {
+        0 => CLType::Bool,
+        1 => CLType::I32,
+        2 => CLType::I64,
+        3 => CLType::U8,
+        4 => CLType::U32,
+        5 => CLType::U64,
+        6 => CLType::U128,
+        7 => CLType::U256,
+        8 => CLType::U512,
+        9 => CLType::Unit,
+        10 => CLType::String,
+        11 => CLType::Key,
+        12 => CLType::URef,
+        13 => CLType::PublicKey,
+        14 => CLType::Any,
+        15 => CLType::ByteArray(*BYTE_ARRAY_SIZES.choose(rng).unwrap()),
+        16 => CLType::Option(nested_type(rng, depth)),
+        17 => CLType::List(nested_type(rng, depth)),
+        18 => CLType::Result {
+            ok: nested_type(rng, depth),
+            err: nested_type(rng, depth),
+        },
+        19 => CLType::Map {
+            key: nested_type(rng, depth),
+            value: nested_type(rng, depth),
+        },
+        20 => CLType::Tuple1([nested_type(rng, depth)]),
+        21 => CLType::Tuple2([nested_type(rng, depth), nested_type(rng, depth)]),
+        _ => CLType::Tuple3([
+            nested_type(rng, depth),
+            nested_type(rng, depth),
+            nested_type(rng, depth),
+        ]),
+    }
+}
+
+fn nested_type<R: Rng>(rng: &mut R, depth: usize) -> Box<CLType> {
+    Box::new(random_type(rng, depth - 1))
+}
+
+/// Returns a random value of `cl_type`.
+pub(crate) fn random_value<R: Rng>(rng: &mut R, cl_type: &CLType) -> RandomValue {
+    match cl_type {
+        CLType::Bool => typed(rng.gen::<bool>()),
+        CLType::I32 => typed(rng.gen::<i32>()),
+        CLType::I64 => typed(rng.gen::<i64>()),
+        CLType::U8 => typed(rng.gen::<u8>()),
+        CLType::U32 => typed(rng.gen::<u32>()),
+        CLType::U64 => typed(rng.gen::<u64>()),
+        // Random number of random bytes, so that both small and huge numbers are generated.
+        CLType::U128 => typed(U128::from_little_endian(&random_bytes(rng, 0..=16))),
+        CLType::U256 => typed(U256::from_little_endian(&random_bytes(rng, 0..=32))),
+        CLType::U512 => typed(U512::from_little_endian(&random_bytes(rng, 0..=64))),
+        CLType::Unit => typed(()),
+        CLType::String => {
+            let len = *STRING_LENGTHS.choose(rng).unwrap();
+            typed(
+                (0..len)
+                    .map(|_| char::from(rng.sample(Alphanumeric)))
+                    .collect::<String>(),
+            )
+        }
+        CLType::Key => typed(random_key(rng)),
+        CLType::URef => typed(random_uref(rng)),
+        CLType::PublicKey => typed(random_public_key(rng)),
+        CLType::ByteArray(size) => RandomValue::Leaf(CLValue::from_components(
+            cl_type.clone(),
+            random_bytes(rng, *size as usize..=*size as usize),
+        )),
+        // Values of `Any` type are opaque bytes.
+        CLType::Any => RandomValue::Leaf(CLValue::from_components(
+            CLType::Any,
+            random_bytes(rng, 0..=16),
+        )),
+        CLType::Option(inner) => RandomValue::Option {
+            inner: (**inner).clone(),
+            value: if rng.gen() {
+                Some(Box::new(random_value(rng, inner)))
+            } else {
+                None
+            },
+        },
+        CLType::List(item) => RandomValue::List {
+            item: (**item).clone(),
+            items: (0..rng.gen_range(0..=MAX_ITEMS))
+                .map(|_| random_value(rng, item))
+                .collect(),
+        },
+        CLType::Result { ok, err } => RandomValue::Result {
+            ok: (**ok).clone(),
+            err: (**err).clone(),
+            value: if rng.gen() {
+                Ok(Box::new(random_value(rng, ok)))
+            } else {
+                Err(Box::new(random_value(rng, err)))
+            },
+        },
+        CLType::Map { key, value } => RandomValue::Map {
+            key: (**key).clone(),
+            value: (**value).clone(),
+            entries: random_entries(rng, key, value),
+        },
+        CLType::Tuple1(types) => RandomValue::Tuple(random_values(rng, types)),
+        CLType::Tuple2(types) => RandomValue::Tuple(random_values(rng, types)),
+        CLType::Tuple3(types) => RandomValue::Tuple(random_values(rng, types)),
+    }
+}
+
+/// Returns random map entries without duplicate keys, sorted the way `BTreeMap` serializes them.
+fn random_entries<R: Rng>(
+    rng: &mut R,
+    key: &CLType,
+    value: &CLType,
+) -> Vec<(RandomValue, RandomValue)> {
+    let mut entries: Vec<(RandomValue, RandomValue)> = (0..rng.gen_range(0..=MAX_ITEMS))
+        .map(|_| {
+            let entry_key = random_value(rng, key);
+            (entry_key, random_value(rng, value))
+        })
+        .collect();
+    entries.sort_by(|(a, _), (b, _)| a.cmp_as_typed(b));
+    entries.dedup_by(|(a, _), (b, _)| a.cmp_as_typed(b) == Ordering::Equal);
+    entries
+}
+
+fn random_values<R: Rng>(rng: &mut R, types: &[Box<CLType>]) -> Vec<RandomValue> {
+    types
+        .iter()
+        .map(|cl_type| random_value(rng, cl_type))
+        .collect()
+}
+
+fn random_bytes<R: Rng>(rng: &mut R, len: std::ops::RangeInclusive<usize>) -> Vec<u8> {
+    let mut bytes = vec![0u8; rng.gen_range(len)];
+    rng.fill(&mut bytes[..]);
+    bytes
+}
+
+fn random_key<R: Rng>(rng: &mut R) -> Key {
+    let bytes: [u8; 32] = rng.gen();
+    match rng.gen_range(0..15) {
+        0 => Key::Account(AccountHash::new(bytes)),
+        1 => Key::Hash(bytes),
+        2 => Key::URef(random_uref(rng)),
+        3 => Key::Transfer(TransferAddr::new(bytes)),
+        4 => Key::DeployInfo(DeployHash::new(bytes)),
+        5 => Key::EraInfo(EraId::new(rng.gen())),
+        6 => Key::Balance(bytes),
+        7 => Key::Bid(AccountHash::new(bytes)),
+        8 => Key::Withdraw(AccountHash::new(bytes)),
+        9 => Key::Dictionary(bytes),
+        10 => Key::SystemContractRegistry,
+        11 => Key::EraSummary,
+        12 => Key::Unbond(AccountHash::new(bytes)),
+        13 => Key::ChainspecRegistry,
+        _ => Key::ChecksumRegistry,
+    }
+}
+
+fn random_uref<R: Rng>(rng: &mut R) -> URef {
+    URef::new(rng.gen(), AccessRights::from_bits_truncate(rng.gen()))
+}
+
+fn random_public_key<R: Rng>(rng: &mut R) -> PublicKey {
+    let bytes: [u8; 32] = rng.gen();
+    let secret_key = match rng.gen_range(0..3) {
+        0 => return PublicKey::System,
+        1 => SecretKey::ed25519_from_bytes(bytes),
+        _ => SecretKey::secp256k1_from_bytes(bytes),
+    };
+    PublicKey::from(&secret_key.expect("random bytes should make a secret key"))
+}
+
+/// Renders a deploy calling a generic contract with the value as its only arg into Ledger pages,
+/// the way its test vector is rendered. Fails with the panic message, if rendering the deploy panics,
+/// or with the page that doesn't fit on the device.
+pub(crate) fn check_rendering(value: &CLValue) -> Result<(), String> {
+    let session = session_sample(
+        "random_arg",
+        ExecutableDeployItem::StoredContractByName {
+            name: "generic-contract".to_string(),
+            entry_point: "generic-txn-entrypoint".to_string(),
+            args: vec![NamedArg::new("arg-0".to_string(), value.clone())].into(),
+        },
+    );
+    let header = HeaderParams::new(TimeDiff::from_seconds(60 * 60), vec![], "casper-test");
+    let deploy = make_deploy_sample(
+        session,
+        system_payment::valid(),
+        header,
+        &random_keys(1),
+        &ChainspecLimits::default(),
+    );
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        ledger::check_deploy_pages(deploy.sample().clone(), &ParserConfig::default())
+    }))
+    .map_err(|payload| {
+        payload
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+            .unwrap_or_default()
+    })
+    .and_then(|pages| pages)
+}
+
+/// Shrinks `value` for as long as a smaller one still `fails`, returns the smallest failing one.
+pub(crate) fn shrink<F: Fn(&RandomValue) -> bool>(value: RandomValue, fails: F) -> RandomValue {
+    let mut value = value;
+    while let Some(smaller) = value
+        .shrink_candidates()
+        .into_iter()
+        .find(|candidate| fails(candidate))
+    {
+        value = smaller;
+    }
+    value
+}
+
+/// Returns sets of random args of random types, split so that none of them serializes
+/// into more than `max_args_length` bytes. Panics on a value that can't be rendered,
+/// with its minimal reproducer.
+pub(crate) fn random_args<R: Rng>(rng: &mut R, max_args_length: u32) -> Vec<RuntimeArgs> {
+    (0..ARGS_SETS_COUNT)
+        .flat_map(|_| {
+            let args = (0..ARGS_COUNT)
+                .map(|idx| {
+                    let cl_type = random_type(rng, MAX_DEPTH);
+                    let value = random_value(rng, &cl_type);
+                    report_failure(&value);
+                    NamedArg::new(format!("arg-{}", idx), value.to_cl_value())
+                })
+                .collect();
+            split_args(args, max_args_length)
+        })
+        .collect()
+}
+
+fn report_failure(value: &RandomValue) {
+    if let Err(failure) = check_rendering(&value.to_cl_value()) {
+        let minimal = shrink(value.clone(), |candidate| {
+            check_rendering(&candidate.to_cl_value()).is_err()
+        });
+        panic!(
+            "random {:?} value can't be rendered: {}, minimal reproducer: {:?}",
+            value.cl_type(),
+            failure,
+            minimal.to_cl_value()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use casper_types::{CLType, CLValue, Key, PublicKey, U512};
+    use rand::SeedableRng;
+
+    use crate::{test_data::rng::StreamRng, utils::cl_value_to_string};
+
+    use super::{
+        check_rendering, random_type, random_value, shrink, typed, RandomValue, MAX_DEPTH,
+    };
+
+    #[test]
+    fn random_types_cover_every_cl_type() {
+        let mut rng = StreamRng::seed_from_u64(0);
+        let variants: BTreeSet<String> = (0..1000)
+            .map(|_| {
+                let cl_type = format!("{:?}", random_type(&mut rng, MAX_DEPTH));
+                cl_type
+                    .split(|c: char| !c.is_alphanumeric())
+                    .next()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        // All the variants of `CLType`, `Any` and `ByteArray` included.
+        assert_eq!(variants.len(), 23, "{:?}", variants);
+    }
+
+    #[test]
+    fn random_values_fit_on_the_device() {
+        let mut rng = StreamRng::seed_from_u64(0);
+        for _ in 0..50 {
+            let cl_type = random_type(&mut rng, MAX_DEPTH);
+            let value = random_value(&mut rng, &cl_type).to_cl_value();
+            assert_eq!(check_rendering(&value), Ok(()), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn random_values_are_well_typed() {
+        let mut rng = StreamRng::seed_from_u64(0);
+        let map = CLType::Map {
+            key: Box::new(CLType::String),
+            value: Box::new(CLType::U512),
+        };
+        let tuple = CLType::Tuple2([Box::new(CLType::PublicKey), Box::new(CLType::ByteArray(32))]);
+        for _ in 0..20 {
+            let value = random_value(&mut rng, &CLType::List(Box::new(CLType::String)));
+            assert!(value.to_cl_value().into_t::<Vec<String>>().is_ok());
+            let value = random_value(&mut rng, &map).to_cl_value();
+            assert!(value.into_t::<BTreeMap<String, U512>>().is_ok());
+            let value = random_value(&mut rng, &CLType::Option(Box::new(CLType::Key)));
+            assert!(value.to_cl_value().into_t::<Option<Key>>().is_ok());
+            let value = random_value(&mut rng, &tuple).to_cl_value();
+            assert!(value.into_t::<(PublicKey, [u8; 32])>().is_ok());
+        }
+    }
+
+    #[test]
+    fn random_maps_are_canonical() {
+        let mut rng = StreamRng::seed_from_u64(0);
+        let map = |key: CLType| CLType::Map {
+            key: Box::new(key),
+            value: Box::new(CLType::Unit),
+        };
+        for _ in 0..50 {
+            // Few possible keys, so that duplicates are drawn.
+            let value = random_value(&mut rng, &map(CLType::Bool)).to_cl_value();
+            let typed: BTreeMap<bool, ()> = value.clone().into_t().unwrap();
+            assert_eq!(CLValue::from_t(typed).unwrap(), value);
+            // Little-endian bytes of the numbers aren't ordered like the numbers.
+            let value = random_value(&mut rng, &map(CLType::U512)).to_cl_value();
+            let typed: BTreeMap<U512, ()> = value.clone().into_t().unwrap();
+            assert_eq!(CLValue::from_t(typed).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn shrinks_to_minimal_reproducer() {
+        let value = RandomValue::List {
+            item: CLType::String,
+            items: vec![typed("a".repeat(100)), typed("b".to_string())],
+        };
+        let too_long = |value: &RandomValue| cl_value_to_string(&value.to_cl_value()).len() > 20;
+        assert!(too_long(&value));
+
+        // The long string alone, halved for as long as it's still too long.
+        let minimal = shrink(value, too_long);
+        assert_eq!(minimal, typed("a".repeat(25)));
+    }
+}
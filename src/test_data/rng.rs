@@ -0,0 +1,76 @@
+//! Independent, deterministic RNG streams derived from the master seed (`CL_TEST_SEED`).
+//!
+//! Every sample category, and every sample within a category, draws from its own stream,
+//! so that adding a sample (or a random call) in one place doesn't change the random values
+//! everywhere after it.
+
+use std::fmt::Display;
+
+use casper_hashing::Digest;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64Mcg;
+
+/// RNG of a single stream.
+pub(crate) type StreamRng = Pcg64Mcg;
+
+/// Seed of a family of RNG streams, each identified by a label.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct StreamSeed([u8; 32]);
+
+impl StreamSeed {
+    /// Draws the seed of the streams from `rng`.
+    pub(crate) fn new<R: Rng>(rng: &mut R) -> Self {
+        StreamSeed(rng.gen())
+    }
+
+    /// Returns the stream identified by `label` - the same seed and label always give the same stream.
+    pub(crate) fn stream(&self, label: &str) -> StreamRng {
+        let digest = Digest::hash([&self.0[..], label.as_bytes()].concat());
+        let mut seed = <StreamRng as SeedableRng>::Seed::default();
+        seed.copy_from_slice(&digest.value()[..seed.len()]);
+        StreamRng::from_seed(seed)
+    }
+}
+
+impl Display for StreamSeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+/// Identifies a single stream: the seed of its family and its label.
+#[derive(Clone, Debug)]
+pub(crate) struct StreamId {
+    seed: StreamSeed,
+    label: String,
+}
+
+impl StreamId {
+    pub(crate) fn new(seed: StreamSeed, label: String) -> Self {
+        StreamId { seed, label }
+    }
+}
+
+impl Display for StreamId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (seed {})", self.label, self.seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+
+    use super::{StreamRng, StreamSeed};
+
+    #[test]
+    fn streams_do_not_depend_on_each_other() {
+        let seed = StreamSeed::new(&mut StreamRng::seed_from_u64(1));
+        let first: u64 = seed.stream("delegate").gen();
+
+        // Drawing from another stream doesn't affect the first one.
+        let _: u64 = seed.stream("undelegate").gen();
+        assert_eq!(first, seed.stream("delegate").gen::<u64>());
+        assert_ne!(first, seed.stream("undelegate").gen::<u64>());
+    }
+}
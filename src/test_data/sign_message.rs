@@ -2,13 +2,43 @@ use crate::{message::CasperMessage, sample::Sample};
 
 const SAMPLE_MESSAGE: &str = "Please sign this CSPR token donation";
 
-/// Returns sample with valid CasperMessage for signing.
+/// Returns samples with valid CasperMessage for signing: printable text displayed as it is
+/// (short, spanning multiple pages, multiline and Unicode) and binary payloads displayed as their hash.
 pub(crate) fn valid_casper_message_sample() -> Vec<Sample<CasperMessage>> {
-    vec![Sample::new(
-        "valid_casper_message",
-        CasperMessage::new(SAMPLE_MESSAGE.as_bytes().to_vec()),
-        true,
-    )]
+    let long = format!(
+        "I agree to the terms of service. {}",
+        "All the tokens are transferred at my own risk. ".repeat(4)
+    );
+    let texts = vec![
+        ("", SAMPLE_MESSAGE.to_string()),
+        ("_short", "Hi".to_string()),
+        ("_long", long),
+        (
+            "_multiline",
+            "Sign in to cspr.live\n\nNonce: 4815162342\r\nIssued at:\t2023-01-01".to_string(),
+        ),
+        (
+            "_unicode",
+            "Zaplať 100 CSPR \u{2013} díky! \u{1F680}".to_string(),
+        ),
+    ];
+    let payloads = vec![
+        ("_binary", vec![0xde, 0xad, 0xbe, 0xef, 0xff, 0x00]),
+        ("_control_chars", b"Please sign\x07\x1b[2J".to_vec()),
+        ("_empty", vec![]),
+    ];
+    texts
+        .into_iter()
+        .map(|(label, text)| (label, text.into_bytes()))
+        .chain(payloads)
+        .map(|(label, msg)| {
+            Sample::new(
+                format!("valid_casper_message{}", label),
+                CasperMessage::new(msg),
+                true,
+            )
+        })
+        .collect()
 }
 
 /// Returns invalid sample of CasperMessage for signing.
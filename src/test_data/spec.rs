@@ -0,0 +1,533 @@
+//! Declarative test-vector specs.
+//!
+//! Every `.toml` or `.json` file in the specs directory (see `CL_SPECS`)
+//! lists `vector`s - each describes the session and payment items, their typed runtime args,
+//! header overrides, the number of signing keys and, optionally, the expected validity.
+//! See `specs/example.toml` for the format.
+
+use std::{
+    convert::TryFrom,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_node::types::{Deploy, DeployHash};
+use casper_types::{
+    account::AccountHash, CLType, CLValue, ContractHash, ContractPackageHash, ContractVersion, Key,
+    NamedArg, PublicKey, RuntimeArgs, TimeDiff, Timestamp, URef, U128, U256, U512,
+};
+use serde::Deserialize;
+
+use crate::{chainspec::ChainspecLimits, sample::Sample};
+
+use super::{
+    commons::{payment_sample, prepend_label, sample_executables, session_sample},
+    make_deploy_sample, random_keys, sample_chain_names, system_payment, without_approvals,
+    HeaderParams, TTL_HOUR,
+};
+
+/// Environment variable with the path to a directory of test-vector specs.
+/// Specs are loaded only when it's set, e.g. to `specs` for the example.
+pub(crate) const SPECS_PATH_ENV: &str = "CL_SPECS";
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SpecFile {
+    #[serde(default, rename = "vector")]
+    vectors: Vec<VectorSpec>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VectorSpec {
+    label: String,
+    session: ItemSpec,
+    /// Defaults to the system payment of 1 CSPR.
+    payment: Option<ItemSpec>,
+    #[serde(default)]
+    header: HeaderSpec,
+    /// Number of keys the deploy is signed with - the first one is the account.
+    #[serde(default = "default_signing_keys")]
+    signing_keys: u8,
+    /// Computed from the validation rules when not set.
+    valid: Option<bool>,
+}
+
+fn default_signing_keys() -> u8 {
+    1
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ItemSpec {
+    ModuleBytes {
+        /// Hex-encoded wasm, empty for the system payment.
+        #[serde(default)]
+        module_bytes: String,
+        #[serde(default)]
+        args: Vec<ArgSpec>,
+    },
+    StoredContractByHash {
+        hash: String,
+        entry_point: String,
+        #[serde(default)]
+        args: Vec<ArgSpec>,
+    },
+    StoredContractByName {
+        name: String,
+        entry_point: String,
+        #[serde(default)]
+        args: Vec<ArgSpec>,
+    },
+    StoredVersionedContractByHash {
+        hash: String,
+        version: Option<ContractVersion>,
+        entry_point: String,
+        #[serde(default)]
+        args: Vec<ArgSpec>,
+    },
+    StoredVersionedContractByName {
+        name: String,
+        version: Option<ContractVersion>,
+        entry_point: String,
+        #[serde(default)]
+        args: Vec<ArgSpec>,
+    },
+    Transfer {
+        #[serde(default)]
+        args: Vec<ArgSpec>,
+    },
+    /// Every stored contract variant calling `entry_point` - one vector per variant.
+    AllStored {
+        entry_point: String,
+        #[serde(default)]
+        args: Vec<ArgSpec>,
+    },
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HeaderSpec {
+    timestamp: Option<Timestamp>,
+    ttl: Option<TimeDiff>,
+    gas_price: Option<u64>,
+    chain_name: Option<String>,
+    #[serde(default)]
+    dependencies: Vec<DeployHash>,
+}
+
+/// Runtime argument, e.g. `{ name = "amount", type = "U512", value = "1000" }`.
+///
+/// Supported types: `Bool`, `I32`, `I64`, `U8`, `U32`, `U64`, `U128`, `U256`, `U512`, `Unit`,
+/// `String`, `PublicKey` (hex), `ByteArray32` (hex), `Key`, `URef` and `AccountHash`
+/// (formatted strings, e.g. `account-hash-..`) and `Option<T>` of any of them (`None` without a value).
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ArgSpec {
+    name: String,
+    #[serde(rename = "type")]
+    cl_type: String,
+    value: Option<ArgValue>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ArgValue {
+    Bool(bool),
+    Int(i64),
+    Str(String),
+}
+
+/// Loads all the specs from `CL_SPECS`. There are none when it's not set.
+///
+/// Returns the samples grouped by the name of the spec file, in alphabetical order of the files.
+pub(super) fn load(chainspec: &ChainspecLimits) -> Vec<(String, Vec<Sample<Deploy>>)> {
+    let dir = match std::env::var(SPECS_PATH_ENV) {
+        Ok(dir) => dir,
+        Err(_) => return vec![],
+    };
+    spec_files(Path::new(&dir))
+        .into_iter()
+        .map(|path| {
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let samples = load_file(&path, chainspec)
+                .unwrap_or_else(|err| panic!("invalid spec {}: {}", path.display(), err));
+            (name, samples)
+        })
+        .collect()
+}
+
+/// Returns the spec files in `dir`, sorted. Missing directory means there are no specs.
+fn spec_files(dir: &Path) -> Vec<PathBuf> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            matches!(
+                path.extension().and_then(|ext| ext.to_str()),
+                Some("toml") | Some("json")
+            )
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn load_file(path: &Path, chainspec: &ChainspecLimits) -> Result<Vec<Sample<Deploy>>, String> {
+    let content = fs::read_to_string(path).map_err(|err| err.to_string())?;
+    let spec_file: SpecFile = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str(&content).map_err(|err| err.to_string())?,
+        _ => toml::from_str(&content).map_err(|err| err.to_string())?,
+    };
+    let mut samples = vec![];
+    for spec in spec_file.vectors {
+        let label = spec.label.clone();
+        samples.extend(
+            spec.into_samples(chainspec)
+                .map_err(|err| format!("vector {}: {}", label, err))?,
+        );
+    }
+    Ok(samples)
+}
+
+impl VectorSpec {
+    fn into_samples(self, chainspec: &ChainspecLimits) -> Result<Vec<Sample<Deploy>>, String> {
+        let VectorSpec {
+            label,
+            session,
+            payment,
+            header,
+            signing_keys,
+            valid,
+        } = self;
+
+        let sessions = match session {
+            ItemSpec::AllStored { entry_point, args } => {
+                sample_executables(&entry_point, runtime_args(&args)?, None)
+                    .into_iter()
+                    .map(|sample| prepend_label(sample, &label))
+                    .collect()
+            }
+            item => vec![session_sample(label, item.into_item()?)],
+        };
+        let payment = match payment {
+            Some(ItemSpec::AllStored { .. }) => {
+                return Err("payment can't be all_stored".to_string())
+            }
+            Some(item) => payment_sample("payment", item.into_item()?),
+            None => system_payment::valid(),
+        };
+
+        let mut header_params = HeaderParams::new(
+            header.ttl.unwrap_or(TTL_HOUR),
+            header.dependencies,
+            header
+                .chain_name
+                .as_deref()
+                .unwrap_or_else(|| chainspec.chain_name()),
+        );
+        if let Some(timestamp) = header.timestamp {
+            header_params = header_params.with_timestamp(timestamp);
+        }
+        if let Some(gas_price) = header.gas_price {
+            header_params = header_params.with_gas_price(gas_price);
+        }
+
+        // Deploy is always signed by its account - approvals are dropped afterwards if there should be none.
+        let keys = random_keys(signing_keys.max(1));
+        let samples = sessions
+            .into_iter()
+            .map(|session| {
+                let label = session.label().to_string();
+                let (_, mut deploy, items_valid) = make_deploy_sample(
+                    session,
+                    payment.clone(),
+                    header_params.clone(),
+                    &keys,
+                    chainspec,
+                )
+                .destructure();
+                if signing_keys == 0 {
+                    deploy = without_approvals(deploy);
+                }
+                let valid = valid.unwrap_or_else(|| {
+                    items_valid
+                        && signing_keys > 0
+                        && sample_chain_names(chainspec)
+                            .iter()
+                            .any(|(_, chain_name)| chain_name == deploy.header().chain_name())
+                });
+                Sample::new(label, deploy, valid)
+            })
+            .collect();
+        Ok(samples)
+    }
+}
+
+impl ItemSpec {
+    fn into_item(self) -> Result<ExecutableDeployItem, String> {
+        let item = match self {
+            ItemSpec::ModuleBytes { module_bytes, args } => ExecutableDeployItem::ModuleBytes {
+                module_bytes: hex::decode(&module_bytes)
+                    .map_err(|err| format!("module_bytes: {}", err))?
+                    .into(),
+                args: runtime_args(&args)?,
+            },
+            ItemSpec::StoredContractByHash {
+                hash,
+                entry_point,
+                args,
+            } => ExecutableDeployItem::StoredContractByHash {
+                hash: ContractHash::new(hash32(&hash)?),
+                entry_point,
+                args: runtime_args(&args)?,
+            },
+            ItemSpec::StoredContractByName {
+                name,
+                entry_point,
+                args,
+            } => ExecutableDeployItem::StoredContractByName {
+                name,
+                entry_point,
+                args: runtime_args(&args)?,
+            },
+            ItemSpec::StoredVersionedContractByHash {
+                hash,
+                version,
+                entry_point,
+                args,
+            } => ExecutableDeployItem::StoredVersionedContractByHash {
+                hash: ContractPackageHash::new(hash32(&hash)?),
+                version,
+                entry_point,
+                args: runtime_args(&args)?,
+            },
+            ItemSpec::StoredVersionedContractByName {
+                name,
+                version,
+                entry_point,
+                args,
+            } => ExecutableDeployItem::StoredVersionedContractByName {
+                name,
+                version,
+                entry_point,
+                args: runtime_args(&args)?,
+            },
+            ItemSpec::Transfer { args } => ExecutableDeployItem::Transfer {
+                args: runtime_args(&args)?,
+            },
+            ItemSpec::AllStored { .. } => {
+                return Err("all_stored expands into multiple items".to_string())
+            }
+        };
+        Ok(item)
+    }
+}
+
+fn runtime_args(args: &[ArgSpec]) -> Result<RuntimeArgs, String> {
+    let named_args = args
+        .iter()
+        .map(|arg| {
+            cl_value(&arg.cl_type, arg.value.as_ref())
+                .map(|value| NamedArg::new(arg.name.clone(), value))
+                .map_err(|err| format!("arg {}: {}", arg.name, err))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(RuntimeArgs::from(named_args))
+}
+
+fn hash32(hex_str: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(hex_str).map_err(|err| err.to_string())?;
+    <[u8; 32]>::try_from(bytes.as_slice())
+        .map_err(|_| format!("expected 32 bytes, found {}", bytes.len()))
+}
+
+/// Returns the type of `Option<T>`, if `cl_type` is one.
+fn option_inner(cl_type: &str) -> Option<&str> {
+    cl_type
+        .strip_prefix("Option<")
+        .and_then(|inner| inner.strip_suffix('>'))
+}
+
+fn cl_type(name: &str) -> Result<CLType, String> {
+    if let Some(inner) = option_inner(name) {
+        return Ok(CLType::Option(Box::new(cl_type(inner)?)));
+    }
+    let cl_type = match name {
+        "Bool" => CLType::Bool,
+        "I32" => CLType::I32,
+        "I64" => CLType::I64,
+        "U8" => CLType::U8,
+        "U32" => CLType::U32,
+        "U64" => CLType::U64,
+        "U128" => CLType::U128,
+        "U256" => CLType::U256,
+        "U512" => CLType::U512,
+        "Unit" => CLType::Unit,
+        "String" => CLType::String,
+        "PublicKey" => CLType::PublicKey,
+        "ByteArray32" => CLType::ByteArray(32),
+        "Key" => CLType::Key,
+        "URef" => CLType::URef,
+        "AccountHash" => CLType::ByteArray(32),
+        _ => return Err(format!("unsupported type {}", name)),
+    };
+    Ok(cl_type)
+}
+
+fn cl_value(cl_type_name: &str, value: Option<&ArgValue>) -> Result<CLValue, String> {
+    if let Some(inner) = option_inner(cl_type_name) {
+        // `Option<T>` is serialized as a `0` tag for `None`, or a `1` tag followed by `T`.
+        let option_type = cl_type(cl_type_name)?;
+        let bytes = match value {
+            None => vec![0],
+            Some(_) => {
+                let mut bytes = vec![1];
+                bytes.extend_from_slice(cl_value(inner, value)?.inner_bytes());
+                bytes
+            }
+        };
+        return Ok(CLValue::from_components(option_type, bytes));
+    }
+
+    let cl_value = match (cl_type_name, value) {
+        ("Unit", None) => CLValue::from_t(()),
+        (_, None) => return Err("missing value".to_string()),
+        ("Bool", Some(ArgValue::Bool(value))) => CLValue::from_t(*value),
+        ("I32", Some(value)) => CLValue::from_t(integer::<i32>(value)?),
+        ("I64", Some(value)) => CLValue::from_t(integer::<i64>(value)?),
+        ("U8", Some(value)) => CLValue::from_t(integer::<u8>(value)?),
+        ("U32", Some(value)) => CLValue::from_t(integer::<u32>(value)?),
+        ("U64", Some(value)) => CLValue::from_t(integer::<u64>(value)?),
+        ("U128", Some(value)) => CLValue::from_t(
+            U128::from_dec_str(&decimal(value)?).map_err(|err| format!("{:?}", err))?,
+        ),
+        ("U256", Some(value)) => CLValue::from_t(
+            U256::from_dec_str(&decimal(value)?).map_err(|err| format!("{:?}", err))?,
+        ),
+        ("U512", Some(value)) => CLValue::from_t(
+            U512::from_dec_str(&decimal(value)?).map_err(|err| format!("{:?}", err))?,
+        ),
+        ("String", Some(ArgValue::Str(value))) => CLValue::from_t(value.clone()),
+        ("PublicKey", Some(ArgValue::Str(value))) => {
+            CLValue::from_t(PublicKey::from_hex(value).map_err(|err| format!("{:?}", err))?)
+        }
+        ("ByteArray32", Some(ArgValue::Str(value))) => CLValue::from_t(hash32(value)?),
+        ("Key", Some(ArgValue::Str(value))) => {
+            CLValue::from_t(Key::from_formatted_str(value).map_err(|err| format!("{:?}", err))?)
+        }
+        ("URef", Some(ArgValue::Str(value))) => {
+            CLValue::from_t(URef::from_formatted_str(value).map_err(|err| format!("{:?}", err))?)
+        }
+        ("AccountHash", Some(ArgValue::Str(value))) => CLValue::from_t(
+            AccountHash::from_formatted_str(value).map_err(|err| format!("{:?}", err))?,
+        ),
+        (cl_type_name, Some(_)) => {
+            // Report unsupported types before mismatched values.
+            cl_type(cl_type_name)?;
+            return Err(format!("value doesn't match type {}", cl_type_name));
+        }
+    };
+    cl_value.map_err(|err| format!("{:?}", err))
+}
+
+/// Reads an integer given either as a number or as a decimal string.
+fn integer<T>(value: &ArgValue) -> Result<T, String>
+where
+    T: TryFrom<i64> + std::str::FromStr,
+{
+    match value {
+        ArgValue::Int(int) => T::try_from(*int).map_err(|_| format!("{} out of range", int)),
+        ArgValue::Str(string) => string
+            .parse()
+            .map_err(|_| format!("{} is not a valid integer", string)),
+        ArgValue::Bool(_) => Err("expected an integer".to_string()),
+    }
+}
+
+/// Reads a decimal number given either as a number or as a string - big numbers don't fit TOML integers.
+fn decimal(value: &ArgValue) -> Result<String, String> {
+    match value {
+        ArgValue::Int(int) if *int >= 0 => Ok(int.to_string()),
+        ArgValue::Str(string) => Ok(string.clone()),
+        _ => Err("expected a non-negative decimal number".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{CLType, U512};
+
+    use super::{cl_value, ArgValue, SpecFile};
+    use crate::chainspec::ChainspecLimits;
+
+    #[test]
+    fn option_args() {
+        let none = cl_value("Option<U64>", None).unwrap();
+        assert_eq!(none.cl_type(), &CLType::Option(Box::new(CLType::U64)));
+        assert_eq!(none.into_t::<Option<u64>>().unwrap(), None);
+
+        let some = cl_value("Option<U64>", Some(&ArgValue::Int(7))).unwrap();
+        assert_eq!(some.into_t::<Option<u64>>().unwrap(), Some(7));
+    }
+
+    #[test]
+    fn spec_validity_is_computed_unless_set() {
+        let spec: SpecFile = toml::from_str(
+            r#"
+            [[vector]]
+            label = "transfer_u64_amount"
+
+            [vector.session]
+            type = "transfer"
+            args = [
+                { name = "amount", type = "U64", value = 1000 },
+                { name = "id", type = "Option<U64>" },
+                { name = "target", type = "ByteArray32", value = "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff" },
+            ]
+
+            [[vector]]
+            label = "delegate"
+            signing_keys = 3
+            header = { gas_price = 1, ttl = "30m" }
+
+            [vector.session]
+            type = "all_stored"
+            entry_point = "delegate"
+            args = [{ name = "amount", type = "U512", value = "500000000000" }]
+            "#,
+        )
+        .unwrap();
+        let chainspec = ChainspecLimits::default();
+        let mut vectors = spec.vectors.into_iter();
+
+        let transfer = vectors.next().unwrap().into_samples(&chainspec).unwrap();
+        assert_eq!(transfer.len(), 1);
+        assert!(!transfer[0].valid());
+
+        let delegate = vectors.next().unwrap().into_samples(&chainspec).unwrap();
+        assert_eq!(delegate.len(), 6);
+        assert!(delegate.iter().all(|sample| sample.valid()));
+        assert_eq!(delegate[0].label(), "delegate__type_by_hash");
+        assert_eq!(delegate[0].sample().approvals().len(), 3);
+        assert_eq!(
+            delegate[0]
+                .sample()
+                .session()
+                .args()
+                .get("amount")
+                .cloned()
+                .unwrap()
+                .into_t::<U512>()
+                .unwrap(),
+            U512::from(500_000_000_000u64)
+        );
+    }
+}
@@ -3,24 +3,29 @@ use casper_types::{bytesrepr::Bytes, runtime_args, RuntimeArgs, U512};
 
 use crate::sample::Sample;
 
+use super::commons::payment_sample;
+
+/// Amount of the system payment samples, in motes.
+pub(super) const PAYMENT_AMOUNT: u64 = 1_000_000_000;
+
 pub(super) fn valid() -> Sample<ExecutableDeployItem> {
     let payment = ExecutableDeployItem::ModuleBytes {
         module_bytes: Bytes::new(),
         args: runtime_args! {
-            "amount" => U512::from(1000000000)
+            "amount" => U512::from(PAYMENT_AMOUNT)
         },
     };
 
-    Sample::new("payment_system", payment, true)
+    payment_sample("payment_system", payment)
 }
 
 pub(super) fn invalid() -> Sample<ExecutableDeployItem> {
     let payment = ExecutableDeployItem::ModuleBytes {
         module_bytes: Bytes::new(),
         args: runtime_args! {
-            "paying" => U512::from(1000000000)
+            "paying" => U512::from(PAYMENT_AMOUNT)
         },
     };
 
-    Sample::new("payment_system_missing_amount", payment, false)
+    payment_sample("payment_system_missing_amount", payment)
 }
@@ -1,5 +1,7 @@
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
 use casper_types::{
-    bytesrepr::FromBytes, CLType, CLValue, Key, PublicKey, URef, ED25519_TAG, SECP256K1_TAG,
+    account::AccountHash, bytesrepr::FromBytes, AccessRights, CLType, CLValue, Key, PublicKey,
+    URef, ED25519_TAG, SECP256K1_TAG, SYSTEM_TAG,
 };
 use itertools::Itertools;
 
@@ -43,7 +45,7 @@ fn drop_key_type_prefix(cl_in: String) -> String {
                 }
                 Key::Dictionary(_) => "dictionary-",
                 Key::SystemContractRegistry => "system-contract-registry-",
-                Key::Unbond(_) => "ubond-",
+                Key::Unbond(_) => "unbond-",
                 Key::ChainspecRegistry => "chainspec-registry",
                 Key::ChecksumRegistry => "checksum-registry",
                 Key::EraSummary => "era-summary-",
@@ -112,16 +114,15 @@ pub(crate) fn cl_value_to_string(cl_in: &CLValue) -> String {
     }
 }
 
+// Values the JSON representation can't express (it has no `parsed` field for them, or fails altogether)
+// are displayed as their raw bytes.
 fn parse_as_default_json(input: &CLValue) -> String {
-    match serde_json::to_value(input) {
-        Ok(value) => {
-            let parsed = value.get("parsed").unwrap();
-            serde_value_to_str(parsed)
-        }
-        Err(err) => {
-            eprintln!("error when parsing CLValue to CLValueJson#Object, {}", err);
-            panic!("{:?}", err)
-        }
+    let parsed = serde_json::to_value(input)
+        .ok()
+        .and_then(|value| value.get("parsed").cloned());
+    match parsed {
+        Some(parsed) => serde_value_to_str(&parsed),
+        None => checksummed_hex::encode(input.inner_bytes().as_slice()),
     }
 }
 
@@ -129,7 +130,7 @@ fn parse_as_default_json(input: &CLValue) -> String {
 // This method drops that prefix (and the closing ')') from the `String` representation for the Ledger.
 pub(crate) fn parse_public_key(key: &PublicKey) -> String {
     let key_tag = match key {
-        PublicKey::System => panic!("Did not expect system key"),
+        PublicKey::System => format!("0{}", SYSTEM_TAG),
         PublicKey::Ed25519(_) => format!("0{}", ED25519_TAG),
         PublicKey::Secp256k1(_) => format!("0{}", SECP256K1_TAG),
         _ => panic!("Should not happen - all key variants are covered at the time of writing"),
@@ -138,3 +139,63 @@ pub(crate) fn parse_public_key(key: &PublicKey) -> String {
     let checksummed_key = checksummed_hex::encode(Into::<Vec<u8>>::into(key));
     format!("{}{}", key_tag, checksummed_key)
 }
+
+/// Returns access rights of the `URef` (or `Key::URef`) stored in the `CLValue`.
+/// Returns `None` for values of any other type.
+pub(crate) fn uref_access_rights(cl_in: &CLValue) -> Option<AccessRights> {
+    let uref = match cl_in.cl_type() {
+        CLType::URef => cl_in.clone().into_t::<URef>().ok(),
+        CLType::Key => cl_in.clone().into_t::<Key>().ok().and_then(Key::into_uref),
+        _ => None,
+    };
+    uref.map(|uref| uref.access_rights())
+}
+
+// Access rights are displayed as the first letters of the granted rights,
+// followed by the octal suffix known from the formatted `URef` - `RAW (007)` for read, add and write.
+pub(crate) fn access_rights_to_string(access_rights: AccessRights) -> String {
+    let granted: String = [
+        (access_rights.is_readable(), 'R'),
+        (access_rights.is_addable(), 'A'),
+        (access_rights.is_writeable(), 'W'),
+    ]
+    .iter()
+    .filter(|(is_granted, _)| *is_granted)
+    .map(|(_, letter)| *letter)
+    .collect();
+    let granted = if granted.is_empty() {
+        "NONE".to_string()
+    } else {
+        granted
+    };
+    format!("{} ({:03o})", granted, access_rights.bits())
+}
+
+/// Returns the account hash derived from the `PublicKey` stored in the `CLValue`.
+/// Returns `None` for values of any other type.
+pub(crate) fn public_key_account_hash(cl_in: &CLValue) -> Option<AccountHash> {
+    match cl_in.cl_type() {
+        CLType::PublicKey => cl_in
+            .clone()
+            .into_t::<PublicKey>()
+            .ok()
+            .map(|public_key| AccountHash::from(&public_key)),
+        _ => None,
+    }
+}
+
+/// Returns the name of the `ExecutableDeployItem` variant, e.g. `StoredContractByHash`.
+pub(crate) fn variant_name(item: &ExecutableDeployItem) -> &'static str {
+    match item {
+        ExecutableDeployItem::ModuleBytes { .. } => "ModuleBytes",
+        ExecutableDeployItem::StoredContractByHash { .. } => "StoredContractByHash",
+        ExecutableDeployItem::StoredContractByName { .. } => "StoredContractByName",
+        ExecutableDeployItem::StoredVersionedContractByHash { .. } => {
+            "StoredVersionedContractByHash"
+        }
+        ExecutableDeployItem::StoredVersionedContractByName { .. } => {
+            "StoredVersionedContractByName"
+        }
+        ExecutableDeployItem::Transfer { .. } => "Transfer",
+    }
+}
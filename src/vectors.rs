@@ -0,0 +1,239 @@
+//! All the test vectors, in the order they're written to `manual.json`.
+//!
+//! Vectors are collected before they're rendered, so that a single one can be looked up
+//! (and explained) by its label or index without rendering the others.
+
+use casper_node::types::Deploy;
+
+use crate::{
+    chainspec::ChainspecLimits,
+    ledger::{self, Element, LimitedLedgerConfig, ZondaxRepr},
+    message::CasperMessage,
+    parser::{
+        self,
+        config::{
+            AmountFormat, AmountUnit, ParserConfig, ThousandsSeparator,
+            DEFAULT_APPROVAL_SIGNERS_LIMIT, DEFAULT_DEPENDENCIES_LIMIT,
+        },
+    },
+    sample::Sample,
+    test_data::{
+        access_rights_samples, adversarial_samples, amount_samples, approvals_samples,
+        combinations::{Combination, Coverage},
+        custom_payment_samples, delegate_samples, generic_samples, header_violation_samples,
+        native_transfer_samples, placement_violation_samples, random_args_samples,
+        redelegate_samples,
+        rng::StreamSeed,
+        sign_message::{invalid_casper_message_sample, valid_casper_message_sample},
+        spec_samples, undelegate_samples,
+    },
+};
+
+/// A single test vector, before it's parsed and paged.
+pub(crate) enum TestVector {
+    Deploy {
+        /// Category of the sample - for random samples also the name of the RNG stream they're drawn from.
+        category: String,
+        sample: Sample<Deploy>,
+        parser_config: ParserConfig,
+    },
+    Message {
+        category: String,
+        sample: Sample<CasperMessage>,
+    },
+}
+
+impl TestVector {
+    pub(crate) fn category(&self) -> &str {
+        match self {
+            TestVector::Deploy { category, .. } | TestVector::Message { category, .. } => category,
+        }
+    }
+
+    pub(crate) fn label(&self) -> &str {
+        match self {
+            TestVector::Deploy { sample, .. } => sample.label(),
+            TestVector::Message { sample, .. } => sample.label(),
+        }
+    }
+
+    /// Returns the deploy sample, unless it's a message vector.
+    pub(crate) fn deploy(&self) -> Option<&Sample<Deploy>> {
+        match self {
+            TestVector::Deploy { sample, .. } => Some(sample),
+            TestVector::Message { .. } => None,
+        }
+    }
+
+    /// Parses the sample into `Element`s, before they're split into Ledger pages.
+    pub(crate) fn elements(&self) -> Vec<Element> {
+        match self {
+            TestVector::Deploy {
+                sample,
+                parser_config,
+                ..
+            } => parser::parse_deploy(sample.sample().clone(), parser_config),
+            TestVector::Message { sample, .. } => parser::parse_message(sample.sample().clone()),
+        }
+    }
+
+    /// Renders the vector in the representation expected by Zondax.
+    pub(crate) fn to_json(&self, index: usize, config: &LimitedLedgerConfig) -> ZondaxRepr {
+        match self {
+            TestVector::Deploy {
+                sample,
+                parser_config,
+                ..
+            } => ledger::deploy_to_json(index, sample.clone(), config, parser_config),
+            TestVector::Message { sample, .. } => {
+                ledger::message_to_json(index, sample.clone(), config)
+            }
+        }
+    }
+}
+
+/// Builds all the test vectors. Each category draws from its own stream of `streams`.
+///
+/// Returns the vectors and the coverage of the sample families whose dimensions are combined
+/// by the `combination` strategy. Composite args of the generic samples are nested up to `composite_depth`.
+pub(crate) fn all(
+    streams: &StreamSeed,
+    chainspec: &ChainspecLimits,
+    parser_config: &ParserConfig,
+    combination: Combination,
+    composite_depth: usize,
+) -> (Vec<TestVector>, Vec<Coverage>) {
+    let deploy_vectors = |category: &str, samples: Vec<Sample<Deploy>>| {
+        let category = category.to_string();
+        samples.into_iter().map(move |sample| TestVector::Deploy {
+            category: category.clone(),
+            sample,
+            parser_config: parser_config.clone(),
+        })
+    };
+
+    let mut vectors: Vec<TestVector> = vec![];
+    vectors.extend(deploy_vectors(
+        "undelegate",
+        undelegate_samples(&mut streams.stream("undelegate"), chainspec),
+    ));
+    vectors.extend(deploy_vectors(
+        "delegate",
+        delegate_samples(&mut streams.stream("delegate"), chainspec),
+    ));
+    let (native_transfers, native_transfer_coverage) = native_transfer_samples(
+        &mut streams.stream("native_transfer"),
+        chainspec,
+        combination,
+    );
+    vectors.extend(deploy_vectors("native_transfer", native_transfers));
+    vectors.extend(deploy_vectors(
+        "redelegate",
+        redelegate_samples(&mut streams.stream("redelegate"), chainspec),
+    ));
+    vectors.extend(deploy_vectors(
+        "generic",
+        generic_samples(&mut streams.stream("generic"), chainspec, composite_depth),
+    ));
+    vectors.extend(deploy_vectors("approvals", approvals_samples(chainspec)));
+    vectors.extend(deploy_vectors(
+        "access_rights",
+        access_rights_samples(&mut streams.stream("access_rights"), chainspec),
+    ));
+    vectors.extend(deploy_vectors(
+        "header_violation",
+        header_violation_samples(&mut streams.stream("header_violation"), chainspec),
+    ));
+
+    vectors.extend(
+        valid_casper_message_sample()
+            .into_iter()
+            .chain(invalid_casper_message_sample())
+            .map(|sample| TestVector::Message {
+                category: "message".to_string(),
+                sample,
+            }),
+    );
+
+    // The same amounts rendered in each of the supported formats,
+    // so that the app and the wallets can agree on one of them.
+    let amount_formats = [
+        (
+            "motes",
+            AmountFormat::new(AmountUnit::Motes, ThousandsSeparator::Space),
+        ),
+        (
+            "cspr",
+            AmountFormat::new(AmountUnit::Cspr, ThousandsSeparator::Space),
+        ),
+        (
+            "cspr_comma",
+            AmountFormat::new(AmountUnit::Cspr, ThousandsSeparator::Comma),
+        ),
+    ];
+    for (format_label, amount_format) in amount_formats.iter() {
+        let amount_parser_config = parser_config.clone().with_amount_format(*amount_format);
+        for mut sample in amount_samples(chainspec) {
+            sample.add_label(format_label.to_string());
+            vectors.push(TestVector::Deploy {
+                category: "amount".to_string(),
+                sample,
+                parser_config: amount_parser_config.clone(),
+            });
+        }
+    }
+
+    // Samples of the families the optional parts of the representation show up in,
+    // drawn from the same streams, rendered with all of the options enabled.
+    let options_parser_config = parser_config
+        .clone()
+        .with_approval_signers(DEFAULT_APPROVAL_SIGNERS_LIMIT)
+        .with_dependencies(DEFAULT_DEPENDENCIES_LIMIT)
+        .with_uref_access_rights()
+        .with_account_hashes();
+    let (options_native_transfers, _) = native_transfer_samples(
+        &mut streams.stream("native_transfer"),
+        chainspec,
+        Combination::NWise(2),
+    );
+    let options_samples = approvals_samples(chainspec)
+        .into_iter()
+        .chain(access_rights_samples(
+            &mut streams.stream("access_rights"),
+            chainspec,
+        ))
+        .chain(delegate_samples(&mut streams.stream("delegate"), chainspec))
+        .chain(options_native_transfers);
+    for mut sample in options_samples {
+        sample.add_label("options".to_string());
+        vectors.push(TestVector::Deploy {
+            category: "options".to_string(),
+            sample,
+            parser_config: options_parser_config.clone(),
+        });
+    }
+
+    vectors.extend(deploy_vectors(
+        "custom_payment",
+        custom_payment_samples(&mut streams.stream("custom_payment"), chainspec),
+    ));
+    vectors.extend(deploy_vectors(
+        "placement_violation",
+        placement_violation_samples(&mut streams.stream("placement_violation"), chainspec),
+    ));
+    vectors.extend(deploy_vectors(
+        "random_args",
+        random_args_samples(&mut streams.stream("random_args"), chainspec),
+    ));
+    vectors.extend(deploy_vectors(
+        "adversarial",
+        adversarial_samples(&mut streams.stream("adversarial"), chainspec),
+    ));
+
+    // Specs come last, so that adding one doesn't shift indices of the built-in vectors.
+    for (category, samples) in spec_samples(chainspec) {
+        vectors.extend(deploy_vectors(&category, samples));
+    }
+
+    (vectors, vec![native_transfer_coverage])
+}